@@ -0,0 +1,174 @@
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Innings};
+use crate::overs::Overs;
+
+/// How the limit of one fifth of an innings per bowler is applied when the innings isn't a
+/// multiple of five overs
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum BowlingRules {
+    /// Each bowler may bowl a fifth of the whole overs (rounded down), with one extra over
+    /// allowed to as few bowlers as are needed to make up the balance. A part over may be
+    /// bowled by one further bowler. This is the ICC's playing condition
+    OneFifth,
+    /// Every bowler may bowl a fifth of the innings rounded up to a whole over
+    OneFifthRoundedUp,
+}
+
+/// The most overs a number of bowlers may bowl. If bowlers is None, the limit applies to any
+/// number of bowlers
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct BowlerLimit {
+    pub overs: Overs,
+    pub bowlers: Option<u16>,
+}
+
+/// The limits on overs per bowler for an innings given its current allocation, as returned by
+/// `CricketMatch::bowler_limits`. Limits are in descending order, and the last applies to every
+/// bowler not covered by an earlier one
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct BowlerLimits {
+    pub innings: Innings,
+    pub innings_overs: Overs,
+    pub limits: Vec<BowlerLimit>,
+}
+
+impl BowlerLimits {
+    /// The most overs any one bowler may bowl
+    pub fn max_overs(&self) -> &Overs {
+        &self.limits[0].overs
+    }
+}
+
+impl Display for BowlerLimits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let limits: Vec<_> = self
+            .limits
+            .iter()
+            .map(|limit| match limit.bowlers {
+                Some(1) => format!("1 bowler may bowl {}", limit.overs),
+                Some(bowlers) => format!("{} bowlers may bowl {}", bowlers, limit.overs),
+                None if self.limits.len() == 1 => format!("each bowler may bowl {}", limit.overs),
+                None => format!("the rest may bowl {}", limit.overs),
+            })
+            .collect();
+        write!(f, "{} overs: {}", self.innings_overs, limits.join(", "))
+    }
+}
+
+impl CricketMatch {
+    /// The maximum overs each bowler may bowl in an innings, based on the overs currently
+    /// allocated to it after every recorded interruption
+    pub fn bowler_limits(&self, innings: &Innings, rules: BowlingRules) -> BowlerLimits {
+        let innings_overs = self.allocation(innings);
+        let balls = innings_overs.total_balls();
+        let whole_overs = balls / 6;
+        let part_over = balls % 6;
+
+        let limits = match rules {
+            BowlingRules::OneFifthRoundedUp => vec![BowlerLimit {
+                overs: Overs::new(balls.div_ceil(30)),
+                bowlers: None,
+            }],
+            BowlingRules::OneFifth => {
+                let standard = whole_overs / 5;
+                let extra_overs = whole_overs % 5;
+                let mut limits = Vec::new();
+                if extra_overs > 0 {
+                    limits.push(BowlerLimit {
+                        overs: Overs::new(standard + 1),
+                        bowlers: Some(extra_overs),
+                    });
+                }
+                if part_over > 0 {
+                    limits.push(BowlerLimit {
+                        overs: Overs::from_balls(standard * 6 + part_over),
+                        bowlers: Some(1),
+                    });
+                }
+                limits.push(BowlerLimit {
+                    overs: Overs::new(standard),
+                    bowlers: None,
+                });
+                limits
+            }
+        };
+
+        BowlerLimits {
+            innings: innings.clone(),
+            innings_overs,
+            limits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bowling::{BowlerLimit, BowlingRules};
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    #[test]
+    fn full_innings_allows_ten_overs_each() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let limits = game.bowler_limits(&Innings::First, BowlingRules::OneFifth);
+
+        assert_eq!(
+            limits.limits,
+            vec![BowlerLimit {
+                overs: Overs::new(10),
+                bowlers: None
+            }]
+        );
+        assert_eq!(limits.to_string(), "50 overs: each bowler may bowl 10");
+    }
+
+    #[test]
+    fn extra_overs_go_to_as_few_bowlers_as_possible() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(12), Innings::Second);
+        let limits = game.bowler_limits(&Innings::Second, BowlingRules::OneFifth);
+
+        assert_eq!(limits.max_overs(), &Overs::new(8));
+        assert_eq!(
+            limits.to_string(),
+            "38 overs: 3 bowlers may bowl 8, the rest may bowl 7"
+        );
+    }
+
+    #[test]
+    fn part_overs_go_to_one_further_bowler() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(
+            2,
+            80,
+            Overs::new(30),
+            "2.4".parse().unwrap(),
+            Innings::First,
+        );
+        let limits = game.bowler_limits(&Innings::First, BowlingRules::OneFifth);
+
+        assert_eq!(limits.innings_overs, "47.2".parse().unwrap());
+        assert_eq!(
+            limits.to_string(),
+            "47.2 overs: 2 bowlers may bowl 10, 1 bowler may bowl 9.2, the rest may bowl 9"
+        );
+    }
+
+    #[test]
+    fn rounding_up_applies_to_every_bowler() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(12), Innings::Second);
+        let limits = game.bowler_limits(&Innings::Second, BowlingRules::OneFifthRoundedUp);
+
+        assert_eq!(limits.to_string(), "38 overs: each bowler may bowl 8");
+    }
+}
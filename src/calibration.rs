@@ -0,0 +1,110 @@
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::cricsheet::CricsheetMatch;
+use crate::overs::Overs;
+use crate::table::DUCKWORTH_LEWIS_TABLE;
+
+/// The z score for a 95% confidence interval
+const Z_95: f32 = 1.96;
+
+/// A G50 value estimated from historical matches, as returned by `estimate_g_50`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct G50Estimate {
+    /// The mean first innings score, scaled up to a full 50 over innings
+    pub g_50: f32,
+    /// The lower bound of a 95% confidence interval for G50
+    pub lower: f32,
+    /// The upper bound of a 95% confidence interval for G50
+    pub upper: f32,
+    /// The number of innings the estimate was based on
+    pub innings: usize,
+}
+
+impl G50Estimate {
+    /// The estimate rounded to the nearest run, suitable for use with
+    /// `CricketMatch::new_with_g_50`
+    pub fn suggested(&self) -> u16 {
+        self.g_50.round() as u16
+    }
+}
+
+/// Estimates G50 from the first innings of historical matches
+///
+/// Each first innings is scaled up to the score that would be expected from a full 50 over
+/// innings by dividing the runs scored by the resources used, so matches of any length up to
+/// 50 overs can be included. A side bowled out for 200 in a 50 over match has used all of its
+/// resources and contributes 200, whereas a side making 150/3 in a 20 over match has used 56.6%
+/// of a full innings's resources and contributes 265
+///
+/// Matches without an overs limit, or longer than 50 overs, are ignored, as are matches decided
+/// by a rain rule (as the first innings may not have been completed under normal conditions)
+/// and matches with no first innings. Returns None if fewer than two innings remain
+pub fn estimate_g_50<'a>(
+    matches: impl IntoIterator<Item = &'a CricsheetMatch>,
+) -> Option<G50Estimate> {
+    let samples: Vec<f32> = matches
+        .into_iter()
+        .filter(|game| !game.rain_affected())
+        .filter_map(|game| {
+            let length = game.length()?;
+            let total = game.regulation_innings().next()?.total();
+            let balls_left = length.total_balls().saturating_sub(total.balls);
+            let used = DUCKWORTH_LEWIS_TABLE.resources_remaining(&length, 0)
+                - DUCKWORTH_LEWIS_TABLE
+                    .resources_remaining(&Overs::from_balls(balls_left), total.wickets);
+            (used > 0.0).then(|| total.runs as f32 * 100.0 / used)
+        })
+        .collect();
+
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let count = samples.len() as f32;
+    let mean = samples.iter().sum::<f32>() / count;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / (count - 1.0);
+    let margin = Z_95 * (variance / count).sqrt();
+
+    Some(G50Estimate {
+        g_50: mean,
+        lower: mean - margin,
+        upper: mean + margin,
+        innings: samples.len(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::calibration::estimate_g_50;
+    use crate::cricsheet::test::SAMPLE;
+    use crate::cricsheet::CricsheetMatch;
+
+    #[test]
+    fn needs_at_least_two_innings() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+
+        assert!(estimate_g_50([&game]).is_none());
+    }
+
+    #[test]
+    fn scales_first_innings_by_resources_used() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+        let estimate = estimate_g_50([&game, &game]).unwrap();
+
+        // 6 runs for 1 wicket from 4 balls uses 7.2% of resources
+        assert_eq!(estimate.innings, 2);
+        assert_eq!(estimate.suggested(), 83);
+        assert_eq!(estimate.lower, estimate.upper);
+    }
+
+    #[test]
+    fn ignores_rain_affected_matches() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+        let mut rain_affected = game.clone();
+        rain_affected.info.outcome.method = Some("D/L".to_owned());
+
+        assert!(estimate_g_50([&game, &rain_affected]).is_none());
+    }
+}
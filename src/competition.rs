@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::CricketMatch;
+use crate::nrr::{NetRunRate, Score};
+use crate::overs::Overs;
+
+/// How many points teams get for each result in a competition. A bonus point can also be given
+/// to a winning team whose run rate was at least `bonus_run_rate_ratio` times their opponent's
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct PointsRules {
+    pub win: u16,
+    pub tie: u16,
+    pub no_result: u16,
+    pub loss: u16,
+    pub bonus: u16,
+    pub bonus_run_rate_ratio: f32,
+}
+
+impl Default for PointsRules {
+    /// Two points for a win, one for a tie or no result, and no bonus points
+    fn default() -> Self {
+        PointsRules {
+            win: 2,
+            tie: 1,
+            no_result: 1,
+            loss: 0,
+            bonus: 0,
+            bonus_run_rate_ratio: 1.25,
+        }
+    }
+}
+
+/// The result of a completed match
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum MatchOutcome {
+    Team1Won,
+    Team2Won,
+    Tie,
+}
+
+/// A team's row in a points table
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Standing {
+    pub team: String,
+    pub played: u16,
+    pub won: u16,
+    pub lost: u16,
+    pub tied: u16,
+    pub no_result: u16,
+    pub bonus_points: u16,
+    pub points: u16,
+    pub net_run_rate: NetRunRate,
+}
+
+/// A points table built up one match at a time, with net run rates adjusted for rain-affected
+/// matches as described in `CricketMatch::net_run_rate_credit`
+#[derive(Debug, Clone)]
+pub struct Standings {
+    rules: PointsRules,
+    rows: Vec<Standing>,
+}
+
+impl CricketMatch {
+    /// The result of the match given both innings' final scores. Team 2 win by passing par
+    /// (rounded down) and the match is tied if they finish exactly on it
+    pub fn outcome(&self, first: &Score, second: &Score) -> MatchOutcome {
+        let par = self.calculation(first.runs as usize).par.floor() as u32;
+        match (second.runs as u32).cmp(&par) {
+            Ordering::Greater => MatchOutcome::Team2Won,
+            Ordering::Equal => MatchOutcome::Tie,
+            Ordering::Less => MatchOutcome::Team1Won,
+        }
+    }
+}
+
+impl Standings {
+    /// Create an empty points table. Teams that haven't played yet are listed with no points;
+    /// any other team is added the first time they play
+    pub fn new(rules: PointsRules, teams: impl IntoIterator<Item = String>) -> Standings {
+        Standings {
+            rules,
+            rows: teams
+                .into_iter()
+                .map(|team| Standing {
+                    team,
+                    ..Standing::default()
+                })
+                .collect(),
+        }
+    }
+
+    /// Add a completed match to the table
+    pub fn add_result(
+        &mut self,
+        team_1: &str,
+        team_2: &str,
+        game: &CricketMatch,
+        first: &Score,
+        second: &Score,
+    ) {
+        let credit = game.net_run_rate_credit(first, second);
+        let outcome = game.outcome(first, second);
+        let rate = |runs: u32, overs: &Overs| runs as f32 * 6.0 / overs.total_balls() as f32;
+        let team_1_rate = rate(credit.team_1_runs, &credit.team_1_overs);
+        let team_2_rate = rate(credit.team_2_runs, &credit.team_2_overs);
+        let rules = self.rules.clone();
+        let bonus = |winner: f32, loser: f32| {
+            if winner >= loser * rules.bonus_run_rate_ratio {
+                rules.bonus
+            } else {
+                0
+            }
+        };
+
+        let (team_1_points, team_2_points) = match outcome {
+            MatchOutcome::Team1Won => (bonus(team_1_rate, team_2_rate), 0),
+            MatchOutcome::Team2Won => (0, bonus(team_2_rate, team_1_rate)),
+            MatchOutcome::Tie => (0, 0),
+        };
+        for (team, batted_first, bonus_points) in [
+            (team_1, true, team_1_points),
+            (team_2, false, team_2_points),
+        ] {
+            let standing = self.standing(team);
+            standing.played += 1;
+            standing.bonus_points += bonus_points;
+            standing.net_run_rate.add(&credit, batted_first);
+            standing.points += bonus_points
+                + match (&outcome, batted_first) {
+                    (MatchOutcome::Tie, _) => {
+                        standing.tied += 1;
+                        rules.tie
+                    }
+                    (MatchOutcome::Team1Won, true) | (MatchOutcome::Team2Won, false) => {
+                        standing.won += 1;
+                        rules.win
+                    }
+                    _ => {
+                        standing.lost += 1;
+                        rules.loss
+                    }
+                };
+        }
+    }
+
+    /// Add a match without a result to the table. It doesn't count towards net run rate
+    pub fn add_no_result(&mut self, team_1: &str, team_2: &str) {
+        let points = self.rules.no_result;
+        for team in [team_1, team_2] {
+            let standing = self.standing(team);
+            standing.played += 1;
+            standing.no_result += 1;
+            standing.points += points;
+        }
+    }
+
+    /// The table ordered by points, then by net run rate
+    pub fn table(&self) -> Vec<Standing> {
+        let mut rows = self.rows.clone();
+        rows.sort_by(|a, b| {
+            b.points.cmp(&a.points).then_with(|| {
+                b.net_run_rate
+                    .value()
+                    .partial_cmp(&a.net_run_rate.value())
+                    .unwrap_or(Ordering::Equal)
+            })
+        });
+        rows
+    }
+
+    fn standing(&mut self, team: &str) -> &mut Standing {
+        let index = match self.rows.iter().position(|row| row.team == team) {
+            Some(index) => index,
+            None => {
+                self.rows.push(Standing {
+                    team: team.to_string(),
+                    ..Standing::default()
+                });
+                self.rows.len() - 1
+            }
+        };
+        &mut self.rows[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::competition::{MatchOutcome, PointsRules, Standings};
+    use crate::{CricketMatch, Grade, Innings, Overs, Score};
+
+    fn score(runs: u16, wickets: u16, overs: u16) -> Score {
+        Score {
+            runs,
+            wickets,
+            overs: Overs::new(overs),
+        }
+    }
+
+    /// Based on ICC example three, where team 2's target was revised to 218 off 40 overs
+    fn shortened_chase() -> CricketMatch {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        game
+    }
+
+    #[test]
+    fn outcome_uses_revised_target() {
+        let game = shortened_chase();
+        let first = score(250, 7, 50);
+
+        assert_eq!(
+            game.outcome(&first, &score(218, 5, 39)),
+            MatchOutcome::Team2Won
+        );
+        assert_eq!(game.outcome(&first, &score(217, 5, 40)), MatchOutcome::Tie);
+        assert_eq!(
+            game.outcome(&first, &score(216, 5, 40)),
+            MatchOutcome::Team1Won
+        );
+    }
+
+    /// Both sides lose 10 overs before team 2 bat, so their resources are equal and par is
+    /// team 1's total
+    #[test]
+    fn outcome_with_equal_resources() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(0, 0, Overs::new(50), Overs::new(10), Innings::First);
+        game.set_team_2_overs(Overs::new(40));
+        let first = score(200, 6, 40);
+
+        assert_eq!(game.revised_par(200), 200);
+        assert_eq!(game.revised_target(200), 201);
+        assert_eq!(game.outcome(&first, &score(200, 8, 40)), MatchOutcome::Tie);
+        assert_eq!(
+            game.outcome(&first, &score(201, 8, 39)),
+            MatchOutcome::Team2Won
+        );
+    }
+
+    #[test]
+    fn table_orders_by_points_then_net_run_rate() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let mut standings = Standings::new(
+            PointsRules::default(),
+            ["A", "B", "C", "D"].map(String::from),
+        );
+        standings.add_result("A", "B", &game, &score(300, 5, 50), &score(150, 10, 30));
+        standings.add_result("C", "B", &game, &score(200, 5, 50), &score(201, 2, 40));
+        standings.add_result("C", "A", &game, &score(220, 10, 50), &score(221, 4, 45));
+        standings.add_no_result("D", "E");
+        let table = standings.table();
+
+        let teams: Vec<_> = table.iter().map(|row| row.team.as_str()).collect();
+        assert_eq!(teams, vec!["A", "B", "D", "E", "C"]);
+        assert_eq!(table[0].points, 4);
+        assert_eq!(table[1].won, 1);
+        assert_eq!(table[1].lost, 1);
+        assert_eq!(table[3].points, 1);
+        assert_eq!(table[4].lost, 2);
+    }
+
+    #[test]
+    fn bonus_point_for_fast_wins() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let rules = PointsRules {
+            bonus: 1,
+            ..PointsRules::default()
+        };
+        let mut standings = Standings::new(rules, []);
+        standings.add_result("A", "B", &game, &score(300, 5, 50), &score(150, 10, 30));
+        standings.add_result("B", "C", &game, &score(250, 5, 50), &score(240, 9, 50));
+        let table = standings.table();
+
+        assert_eq!(table[0].team, "A");
+        assert_eq!(table[0].points, 3);
+        assert_eq!(table[0].bonus_points, 1);
+        assert_eq!(table[1].points, 2);
+        assert_eq!(table[1].bonus_points, 0);
+    }
+}
@@ -0,0 +1,541 @@
+use std::io::Read;
+
+use serde::Deserialize;
+
+use crate::game::{CricketMatch, Grade, Innings};
+use crate::overs::Overs;
+use crate::DuckworthLewisError;
+
+/// Dismissals that don't count as a wicket for the purposes of resources remaining
+const NOT_DISMISSALS: [&str; 2] = ["retired hurt", "retired not out"];
+
+/// A match in the Cricsheet JSON format (https://cricsheet.org/format/json/). Only the parts of
+/// the format needed by this crate are read; everything else is ignored
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetMatch {
+    pub info: CricsheetInfo,
+    #[serde(default)]
+    pub innings: Vec<CricsheetInnings>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetInfo {
+    /// The scheduled overs per innings. Not present for matches without an overs limit
+    pub overs: Option<u16>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+    #[serde(default)]
+    pub outcome: CricsheetOutcome,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CricsheetOutcome {
+    pub winner: Option<String>,
+    /// Set when the result was decided by a rain rule, e.g. "D/L"
+    pub method: Option<String>,
+    /// Set when there was no winner, e.g. "tie" or "no result"
+    pub result: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetInnings {
+    pub team: String,
+    #[serde(default)]
+    pub overs: Vec<CricsheetOver>,
+    pub target: Option<CricsheetTarget>,
+    #[serde(default)]
+    pub super_over: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetOver {
+    pub over: u16,
+    pub deliveries: Vec<CricsheetDelivery>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetDelivery {
+    pub runs: CricsheetRuns,
+    pub extras: Option<CricsheetExtras>,
+    #[serde(default)]
+    pub wickets: Vec<CricsheetWicket>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetRuns {
+    pub total: u16,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CricsheetExtras {
+    pub wides: Option<u16>,
+    pub noballs: Option<u16>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetWicket {
+    pub kind: String,
+}
+
+/// The revised target for the second innings, recorded when a match has been shortened
+#[derive(Debug, Clone, Deserialize)]
+pub struct CricsheetTarget {
+    pub overs: Option<f32>,
+    pub runs: u16,
+}
+
+/// The score in an innings after a legal delivery has been bowled
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Progress {
+    pub balls: u16,
+    pub wickets: u16,
+    pub runs: u16,
+}
+
+/// A Cricsheet match converted to a `CricketMatch`, as returned by `CricsheetMatch::import`
+#[derive(Clone)]
+pub struct ImportedMatch {
+    pub game: CricketMatch,
+    pub first_innings_total: u16,
+    /// The target recorded in the Cricsheet data, if there was one
+    pub recorded_target: Option<u16>,
+    /// The target this crate calculates for the reconstructed match
+    pub computed_target: u32,
+}
+
+impl ImportedMatch {
+    /// How many runs the computed target is above (or below, if negative) the recorded one
+    pub fn difference(&self) -> Option<i64> {
+        self.recorded_target
+            .map(|recorded| self.computed_target as i64 - recorded as i64)
+    }
+}
+
+impl CricsheetMatch {
+    /// Read a match from Cricsheet JSON
+    pub fn from_reader<R: Read>(reader: R) -> Result<CricsheetMatch, DuckworthLewisError> {
+        serde_json::from_reader(reader)
+            .map_err(|err| DuckworthLewisError::InvalidCricsheet(err.to_string()))
+    }
+
+    /// The scheduled length of each innings, if this was a limited overs match of no more than
+    /// 50 overs
+    pub fn length(&self) -> Option<Overs> {
+        self.info.overs.filter(|overs| *overs <= 50).map(Overs::new)
+    }
+
+    /// Whether a rain rule was used to decide the result
+    pub fn rain_affected(&self) -> bool {
+        self.info.outcome.method.is_some()
+    }
+
+    /// The first and second innings of the match, ignoring any super overs
+    pub fn regulation_innings(&self) -> impl Iterator<Item = &CricsheetInnings> {
+        self.innings
+            .iter()
+            .filter(|innings| !innings.super_over)
+            .take(2)
+    }
+
+    /// Converts this match into a `CricketMatch` for the given grade, and calculates the target
+    /// for the team batting second so it can be compared against the one recorded
+    ///
+    /// Cricsheet doesn't record when play stopped, so interruptions are reconstructed from the
+    /// deliveries instead:
+    /// * where the over numbers in an innings jump, the missing overs are treated as lost to an
+    ///   interruption after the last ball before the gap, using the wickets and runs at that ball
+    /// * if the first innings ended with wickets in hand short of its allocation, it is treated
+    ///   as terminated at that point
+    /// * any difference between the overs in the recorded target and the overs team 2 would
+    ///   otherwise have had, after allowing for gaps in their innings, is treated as a reduction
+    ///   made before their innings started
+    ///
+    /// A match reduced before the start in which team 1 batted all of their overs looks the same
+    /// as one where the first innings was curtailed, so it is imported as a curtailed innings
+    pub fn import(&self, grade: Grade) -> Result<ImportedMatch, DuckworthLewisError> {
+        self.import_into(CricketMatch::new(self.limited_length()?, grade))
+    }
+
+    /// As `import`, but using a custom G50 rather than the one for a grade
+    pub fn import_with_g_50(&self, g_50: u16) -> Result<ImportedMatch, DuckworthLewisError> {
+        self.import_into(CricketMatch::new_with_g_50(self.limited_length()?, g_50))
+    }
+
+    fn limited_length(&self) -> Result<Overs, DuckworthLewisError> {
+        self.length().ok_or_else(|| {
+            DuckworthLewisError::InvalidCricsheet(
+                "not a limited overs match of 50 overs or less".to_string(),
+            )
+        })
+    }
+
+    fn import_into(&self, mut game: CricketMatch) -> Result<ImportedMatch, DuckworthLewisError> {
+        let mut innings = self.regulation_innings();
+        let first = innings.next().ok_or_else(|| {
+            DuckworthLewisError::InvalidCricsheet("no innings played".to_string())
+        })?;
+        let second = innings.next();
+
+        first.replay_gaps(&mut game, Innings::First);
+        let first_total = first.total();
+        let bowled = Overs::from_balls(first_total.balls);
+        if first_total.wickets < 10 && bowled < game.allocation(&Innings::First) {
+            game.terminate_first_innings(first_total.wickets, first_total.runs, bowled);
+        }
+
+        let recorded = second.and_then(|second| second.target.as_ref());
+        let recorded_overs = recorded
+            .and_then(|target| target.overs)
+            .map(overs_from_decimal)
+            .filter(|overs| overs <= game.length());
+        if let (Some(second), Some(overs)) = (second, recorded_overs) {
+            let starting_overs = Overs::from_balls(overs.total_balls() + second.gap_overs() * 6)
+                .min(game.length().clone());
+            let allocation = game.allocation(&Innings::Second);
+            if starting_overs < allocation {
+                game.delay_between_innings(allocation - starting_overs);
+            } else if starting_overs > allocation {
+                game.set_team_2_overs(starting_overs);
+            }
+        }
+        if let Some(second) = second {
+            second.replay_gaps(&mut game, Innings::Second);
+        }
+
+        let computed_target = game.calculation(first_total.runs as usize).target;
+        Ok(ImportedMatch {
+            game,
+            first_innings_total: first_total.runs,
+            recorded_target: recorded.map(|target| target.runs),
+            computed_target,
+        })
+    }
+}
+
+/// A jump in the over numbers of an innings, as found by `CricsheetInnings::gaps`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OverGap {
+    /// The score after the last legal ball before the gap
+    pub at: Progress,
+    pub overs_missing: u16,
+}
+
+impl CricsheetInnings {
+    /// The score after each legal delivery in the innings, in order. Runs and wickets from wides
+    /// and no balls are included in the next legal delivery
+    pub fn progress(&self) -> Vec<Progress> {
+        let mut progress = Vec::new();
+        let mut runs = 0;
+        let mut wickets = 0;
+        let mut balls = 0;
+        for delivery in self.overs.iter().flat_map(|over| &over.deliveries) {
+            runs += delivery.runs.total;
+            wickets += delivery
+                .wickets
+                .iter()
+                .filter(|wicket| !NOT_DISMISSALS.contains(&wicket.kind.as_str()))
+                .count() as u16;
+            if delivery.is_legal() {
+                balls += 1;
+                progress.push(Progress {
+                    balls,
+                    wickets: wickets.min(10),
+                    runs,
+                });
+            }
+        }
+        if let Some(last) = progress.last_mut() {
+            last.runs = runs;
+            last.wickets = wickets.min(10);
+        }
+        progress
+    }
+
+    /// Each place the over numbers in the innings jump, in order. A gap before the first over
+    /// is reported at the start of the innings
+    pub fn gaps(&self) -> Vec<OverGap> {
+        let progress = self.progress();
+        let mut gaps = Vec::new();
+        let mut balls: usize = 0;
+        let mut expected = 0;
+        for over in &self.overs {
+            if over.over > expected {
+                gaps.push(OverGap {
+                    at: balls
+                        .checked_sub(1)
+                        .and_then(|ball| progress.get(ball))
+                        .cloned()
+                        .unwrap_or(Progress {
+                            balls: 0,
+                            wickets: 0,
+                            runs: 0,
+                        }),
+                    overs_missing: over.over - expected,
+                });
+            }
+            balls += over
+                .deliveries
+                .iter()
+                .filter(|delivery| delivery.is_legal())
+                .count();
+            expected = over.over + 1;
+        }
+        gaps
+    }
+
+    /// The total overs missing from gaps in the innings
+    fn gap_overs(&self) -> u16 {
+        self.gaps().iter().map(|gap| gap.overs_missing).sum()
+    }
+
+    /// Records an interruption against the match for each gap in the innings, with the missing
+    /// overs lost. Gaps once the innings is all out or has no overs left are ignored
+    fn replay_gaps(&self, game: &mut CricketMatch, innings: Innings) {
+        for gap in self.gaps() {
+            let overs_left = game.allocation(&innings) - Overs::from_balls(gap.at.balls);
+            if gap.at.wickets >= 10 || overs_left == Overs::new(0) {
+                continue;
+            }
+            let overs_lost = Overs::new(gap.overs_missing).min(overs_left.clone());
+            game.interruption_with_runs(
+                gap.at.wickets,
+                gap.at.runs,
+                overs_left,
+                overs_lost,
+                innings.clone(),
+            );
+        }
+    }
+
+    /// The score at the end of the innings
+    pub fn total(&self) -> Progress {
+        self.progress().pop().unwrap_or(Progress {
+            balls: 0,
+            wickets: 0,
+            runs: 0,
+        })
+    }
+}
+
+impl CricsheetDelivery {
+    fn is_legal(&self) -> bool {
+        self.extras
+            .as_ref()
+            .map(|extras| extras.wides.is_none() && extras.noballs.is_none())
+            .unwrap_or(true)
+    }
+}
+
+/// Cricsheet records overs as a decimal, e.g. 38.4 for 38 overs and 4 balls
+fn overs_from_decimal(overs: f32) -> Overs {
+    let whole = overs.trunc();
+    let balls = ((overs - whole) * 10.0).round() as u16;
+    Overs::from_balls(whole as u16 * 6 + balls.min(5))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::cricsheet::{CricsheetMatch, Progress};
+    use crate::{Grade, Innings, Overs};
+
+    /// A cut down Cricsheet match with a four ball first innings and a two ball chase
+    pub(crate) const SAMPLE: &str = r#"{
+        "meta": {"data_version": "1.1.0"},
+        "info": {
+            "overs": 50,
+            "teams": ["Home", "Away"],
+            "outcome": {"winner": "Home", "by": {"runs": 2}}
+        },
+        "innings": [
+            {
+                "team": "Home",
+                "overs": [
+                    {"over": 0, "deliveries": [
+                        {"batter": "a", "runs": {"batter": 4, "extras": 0, "total": 4}},
+                        {"batter": "a", "runs": {"batter": 0, "extras": 1, "total": 1}, "extras": {"wides": 1}},
+                        {"batter": "a", "runs": {"batter": 0, "extras": 0, "total": 0},
+                         "wickets": [{"player_out": "a", "kind": "bowled"}]}
+                    ]},
+                    {"over": 1, "deliveries": [
+                        {"batter": "b", "runs": {"batter": 1, "extras": 0, "total": 1}},
+                        {"batter": "c", "runs": {"batter": 0, "extras": 0, "total": 0},
+                         "wickets": [{"player_out": "c", "kind": "retired hurt"}]}
+                    ]}
+                ]
+            },
+            {
+                "team": "Away",
+                "target": {"overs": 50, "runs": 7},
+                "overs": [
+                    {"over": 0, "deliveries": [
+                        {"batter": "d", "runs": {"batter": 2, "extras": 0, "total": 2}},
+                        {"batter": "d", "runs": {"batter": 0, "extras": 0, "total": 0},
+                         "wickets": [{"player_out": "d", "kind": "caught"}]}
+                    ]}
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn reads_progress_through_innings() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+        let first = game.regulation_innings().next().unwrap();
+
+        assert_eq!(first.progress().len(), 4);
+        assert_eq!(
+            first.total(),
+            Progress {
+                balls: 4,
+                wickets: 1,
+                runs: 6
+            }
+        );
+        assert_eq!(game.length(), Some(50.into()));
+        assert!(!game.rain_affected());
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(CricsheetMatch::from_reader("{".as_bytes()).is_err());
+    }
+
+    /// Cricsheet JSON for an innings with the given over numbers. Each of the first `runs` balls
+    /// scores a run, and a wicket falls on each of the balls (counted from 1) in `wickets`
+    fn innings(
+        team: &str,
+        overs: impl IntoIterator<Item = u16>,
+        runs: usize,
+        wickets: &[usize],
+    ) -> String {
+        let mut ball = 0;
+        let overs: Vec<_> = overs
+            .into_iter()
+            .map(|over| {
+                let deliveries: Vec<_> = (0..6)
+                    .map(|_| {
+                        ball += 1;
+                        let runs = if ball <= runs { 1 } else { 0 };
+                        let wicket = if wickets.contains(&ball) {
+                            r#", "wickets": [{"player_out": "x", "kind": "caught"}]"#
+                        } else {
+                            ""
+                        };
+                        format!(r#"{{"batter": "x", "runs": {{"batter": {runs}, "extras": 0, "total": {runs}}}{wicket}}}"#)
+                    })
+                    .collect();
+                format!(r#"{{"over": {}, "deliveries": [{}]}}"#, over, deliveries.join(","))
+            })
+            .collect();
+        format!(r#"{{"team": "{}", "overs": [{}]"#, team, overs.join(","))
+    }
+
+    fn rain_affected_match(first: String, second: String) -> CricsheetMatch {
+        let json = format!(
+            r#"{{
+                "info": {{"overs": 50, "teams": ["Home", "Away"], "outcome": {{"winner": "Home", "method": "D/L"}}}},
+                "innings": [{}}}, {}]
+            }}"#,
+            first, second
+        );
+        CricsheetMatch::from_reader(json.as_bytes()).unwrap()
+    }
+
+    /// ICC example one: team 1 are 3 down after 20 overs when 10 overs are lost, then make 180
+    /// in 40 overs. Team 2's target is 185 in 40 overs
+    #[test]
+    fn gap_in_first_innings_is_an_interruption() {
+        let first = innings("Home", (0..20).chain(30..50), 180, &[10, 50, 100]);
+        let second = innings("Away", 0..10, 60, &[]) + r#", "target": {"overs": 40, "runs": 185}}"#;
+        let imported = rain_affected_match(first, second)
+            .import(Grade::ICCFullMember)
+            .unwrap();
+
+        let interruptions = imported.game.interruption_summaries(180);
+        assert_eq!(interruptions.len(), 1);
+        assert_eq!(interruptions[0].wickets, 3);
+        assert_eq!(interruptions[0].overs_lost, Overs::new(10));
+        assert_eq!(imported.game.allocation(&Innings::Second), Overs::new(40));
+        assert_eq!(imported.computed_target, 185);
+        assert_eq!(imported.difference(), Some(0));
+    }
+
+    /// ICC example three: team 1 make 250 in 50 overs, then team 2 are 55/1 after 12 overs when
+    /// 10 overs are lost. Team 2's target is 218 in 40 overs
+    #[test]
+    fn gap_in_second_innings_is_an_interruption() {
+        let first = innings("Home", 0..50, 250, &[30, 90, 150, 200, 240, 270, 290]);
+        let second = innings("Away", (0..12).chain(22..50), 55, &[40])
+            + r#", "target": {"overs": 40, "runs": 218}}"#;
+        let imported = rain_affected_match(first, second)
+            .import(Grade::ICCFullMember)
+            .unwrap();
+
+        let interruptions = imported.game.interruption_summaries(250);
+        assert_eq!(interruptions.len(), 1);
+        assert_eq!(interruptions[0].runs, Some(55));
+        assert_eq!(interruptions[0].overs_faced, Overs::new(12));
+        assert_eq!(imported.computed_target, 218);
+        assert_eq!(imported.difference(), Some(0));
+    }
+
+    /// Team 1 are 150/4 after 30 overs when their innings is ended, and team 2 are given 30 overs.
+    /// Team 1 lose the 44.6% resources left and team 2 have 75.1%, so par is
+    /// 150 + 245 × (75.1 - 55.4) / 100 = 198.27
+    #[test]
+    fn curtailed_first_innings_is_terminated() {
+        let first = innings("Home", 0..30, 150, &[20, 60, 100, 140]);
+        let second = innings("Away", 0..10, 60, &[]) + r#", "target": {"overs": 30, "runs": 199}}"#;
+        let imported = rain_affected_match(first, second)
+            .import(Grade::ICCFullMember)
+            .unwrap();
+
+        let interruptions = imported.game.interruption_summaries(150);
+        assert_eq!(interruptions.len(), 1);
+        assert_eq!(interruptions[0].overs_lost, Overs::new(20));
+        assert_eq!(imported.game.allocation(&Innings::Second), Overs::new(30));
+        assert_eq!(imported.computed_target, 199);
+        assert_eq!(imported.difference(), Some(0));
+    }
+
+    /// Team 1 make 250 in 50 overs and team 2 are given 40 overs before their innings starts, so
+    /// par is 250 × 89.3 / 100 = 223.25
+    #[test]
+    fn reduction_before_the_chase_is_a_delay_between_innings() {
+        let first = innings("Home", 0..50, 250, &[30, 90, 150]);
+        let second =
+            innings("Away", 0..40, 200, &[]) + r#", "target": {"overs": 40, "runs": 224}}"#;
+        let imported = rain_affected_match(first, second)
+            .import(Grade::ICCFullMember)
+            .unwrap();
+
+        assert!(imported.game.interruption_summaries(250).is_empty());
+        assert_eq!(imported.game.allocation(&Innings::Second), Overs::new(40));
+        assert_eq!(imported.computed_target, 224);
+        assert_eq!(imported.difference(), Some(0));
+    }
+
+    /// The sample's first innings ends after 4 balls at 6/1, losing 92.8% resources, and team 2
+    /// are given 50 overs, so par is 6 + 200 × 92.8 / 100 = 191.6
+    #[test]
+    fn terminated_first_innings_against_a_full_chase() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+        let imported = game.import_with_g_50(200).unwrap();
+
+        assert_eq!(
+            imported.game.allocation(&Innings::First),
+            "0.4".parse().unwrap()
+        );
+        assert_eq!(imported.game.allocation(&Innings::Second), Overs::new(50));
+        assert_eq!(imported.recorded_target, Some(7));
+        assert_eq!(imported.computed_target, 192);
+    }
+
+    #[test]
+    fn rejects_matches_without_overs_limit() {
+        let unlimited = SAMPLE.replace(r#""overs": 50,"#, "");
+        let game = CricsheetMatch::from_reader(unlimited.as_bytes()).unwrap();
+
+        assert!(game.import(Grade::ICCFullMember).is_err());
+    }
+}
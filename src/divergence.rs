@@ -0,0 +1,139 @@
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::CricketMatch;
+
+/// A warning that the first innings total is far enough above G50 that the standard edition
+/// target is likely to differ from the one the Professional Edition would give, as returned by
+/// `CricketMatch::divergence_warning`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct DivergenceWarning {
+    pub first_innings_total: usize,
+    pub g_50: f32,
+    /// The first innings total as a multiple of G50
+    pub ratio: f32,
+    /// The multiple of G50 above which the warning is given
+    pub threshold: f32,
+    /// An estimate of how many runs higher the target would be if team 2's extra resources were
+    /// valued at the rate team 1 actually scored, rather than at G50
+    pub expected_divergence: f32,
+}
+
+impl Display for DivergenceWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "First innings total of {} is {:.2} times G50 ({}), above the threshold of {:.2}; ",
+            self.first_innings_total, self.ratio, self.g_50, self.threshold
+        )?;
+        if self.expected_divergence >= 0.5 {
+            write!(
+                f,
+                "the Professional Edition would likely set a target about {:.0} runs higher",
+                self.expected_divergence
+            )
+        } else {
+            write!(f, "the target may differ from the Professional Edition")
+        }
+    }
+}
+
+impl CricketMatch {
+    /// Checks whether the first innings total is well above G50, where the standard edition is
+    /// known to be unfair to the team batting first
+    ///
+    /// When team 2 have more resources than team 1, the standard edition adds G50 runs for
+    /// every 100% of extra resources, however quickly team 1 actually scored. The expected
+    /// divergence values those resources at team 1's rate instead, which is roughly what the
+    /// Professional Edition does. When team 2 have fewer resources the target already scales
+    /// with the first innings total, so the expected divergence is 0, although the Professional
+    /// Edition's resource percentages would still differ a little
+    ///
+    /// Returns None if the first innings total is no more than the threshold multiple of G50, or
+    /// if no resources have been lost, since the target is then just one more than the total
+    pub fn divergence_warning(&self, first_innings_total: usize) -> Option<DivergenceWarning> {
+        let ratio = first_innings_total as f32 / self.g_50();
+        let threshold = self.divergence_threshold();
+        if ratio <= threshold {
+            return None;
+        }
+
+        let calculation = self.calculation(first_innings_total);
+        let resources_lost = !calculation.interruptions.is_empty()
+            || calculation.team_2_resources < calculation.team_1_resources;
+        if !resources_lost {
+            return None;
+        }
+        let extra_resources =
+            (calculation.team_2_resources - calculation.team_1_resources).max(0.0);
+        let team_1_rate = first_innings_total as f32 / calculation.team_1_resources;
+        let expected_divergence = extra_resources * (team_1_rate - self.g_50() / 100.0);
+
+        Some(DivergenceWarning {
+            first_innings_total,
+            g_50: self.g_50(),
+            ratio,
+            threshold,
+            expected_divergence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    fn shortened_chase() -> CricketMatch {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.delay_between_innings(Overs::new(10));
+        game
+    }
+
+    #[test]
+    fn no_warning_near_g_50() {
+        let game = shortened_chase();
+
+        assert!(game.divergence_warning(280).is_none());
+        assert!(game.divergence_warning(300).is_some());
+    }
+
+    #[test]
+    fn no_warning_without_lost_resources() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+
+        assert!(game.divergence_warning(400).is_none());
+    }
+
+    #[test]
+    fn threshold_can_be_configured() {
+        let game = shortened_chase().with_divergence_threshold(1.5);
+
+        assert!(game.divergence_warning(300).is_none());
+        assert!(game.divergence_warning(380).is_some());
+    }
+
+    /// Based on ICC example one, with a first innings total far above G50
+    #[test]
+    fn divergence_grows_with_extra_resources() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(3, 95, Overs::new(30), Overs::new(10), Innings::First);
+        let warning = game.divergence_warning(360).unwrap();
+
+        let calculation = game.calculation(360);
+        let proportional = 360.0 * calculation.team_2_resources / calculation.team_1_resources;
+        assert!((warning.expected_divergence - (proportional - calculation.par)).abs() < 0.01);
+        assert!(warning.expected_divergence > 1.0);
+    }
+
+    #[test]
+    fn no_divergence_expected_when_team_2_has_fewer_resources() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        let warning = game.divergence_warning(360).unwrap();
+
+        assert_eq!(warning.expected_divergence, 0.0);
+    }
+}
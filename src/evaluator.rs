@@ -0,0 +1,270 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::game::{target, CricketMatch, Innings};
+use crate::overs::Overs;
+
+/// The number of entries per ball in the precomputed resources, one for each of 0 to 10 wickets
+const WICKET_STATES: usize = 11;
+
+/// An interruption in a scenario evaluated by `BatchEvaluator`. Fields have the same meaning as
+/// the arguments to `CricketMatch::interruption`
+#[derive(Debug, Clone)]
+pub struct ScenarioInterruption {
+    pub innings: Innings,
+    pub wickets: u16,
+    pub overs_left: Overs,
+    pub overs_lost: Overs,
+}
+
+/// A first innings total and the interruptions that occurred, to be evaluated against a match
+/// configuration by `BatchEvaluator`
+#[derive(Debug, Clone)]
+pub struct Scenario<'a> {
+    pub first_innings_total: usize,
+    pub interruptions: &'a [ScenarioInterruption],
+}
+
+/// Calculates revised targets for large numbers of scenarios against a single match
+/// configuration (i.e. match length and G50), such as when running simulations
+///
+/// The resources remaining for every ball and wicket combination are looked up once when the
+/// evaluator is created, and scenarios borrow their interruptions, so evaluating a scenario
+/// doesn't allocate. With the `parallel` feature enabled, scenarios can also be evaluated across
+/// threads using rayon
+///
+/// Results are the same as would be returned by `CricketMatch::revised_target` if each
+/// interruption in the scenario was recorded against the match
+pub struct BatchEvaluator {
+    g_50: f32,
+    length: u16,
+    resources: Vec<f32>,
+}
+
+impl BatchEvaluator {
+    /// Create an evaluator using the length, G50 and resource table of the given match. Any events already
+    /// recorded for the match are ignored
+    pub fn new(game: &CricketMatch) -> BatchEvaluator {
+        let length = game.as_at(0).allocation(&Innings::First).total_balls();
+        let table = game.table();
+        let resources = (0..=length)
+            .flat_map(|balls| {
+                (0..WICKET_STATES as u16).map(move |wickets| {
+                    table.resources_remaining(&Overs::from_balls(balls), wickets)
+                })
+            })
+            .collect();
+        BatchEvaluator {
+            g_50: game.g_50(),
+            length,
+            resources,
+        }
+    }
+
+    /// The revised target for team 2 in a single scenario. As with `CricketMatch::revised_target`
+    /// this will return 0 if the scenario has no interruptions
+    ///
+    /// Panics
+    /// Wickets must be no more than 10
+    /// Overs left must be less than or equal to the length of the match
+    pub fn evaluate(&self, scenario: &Scenario) -> u32 {
+        if scenario.interruptions.is_empty() {
+            return 0;
+        }
+
+        let mut t1_resources = self.remaining(self.length, 0);
+        let mut t2_balls = self.length;
+        for int in scenario
+            .interruptions
+            .iter()
+            .filter(|int| int.innings == Innings::First)
+        {
+            t1_resources -= self.resource_loss(int);
+            t2_balls = t2_balls.saturating_sub(int.overs_lost.total_balls());
+        }
+
+        let t2_resources = scenario
+            .interruptions
+            .iter()
+            .filter(|int| int.innings == Innings::Second)
+            .fold(self.remaining(t2_balls, 0), |resources, int| {
+                resources - self.resource_loss(int)
+            });
+
+        target(
+            self.g_50,
+            scenario.first_innings_total,
+            t1_resources,
+            t2_resources,
+        )
+    }
+
+    /// Evaluates each scenario in turn, writing the revised targets into the matching position
+    /// of `targets`
+    ///
+    /// Panics
+    /// Scenarios and targets must be the same length
+    pub fn evaluate_into(&self, scenarios: &[Scenario], targets: &mut [u32]) {
+        assert_eq!(scenarios.len(), targets.len());
+        for (scenario, target) in scenarios.iter().zip(targets.iter_mut()) {
+            *target = self.evaluate(scenario);
+        }
+    }
+
+    /// Evaluates scenarios in parallel, writing the revised targets into the matching position
+    /// of `targets`
+    ///
+    /// Panics
+    /// Scenarios and targets must be the same length
+    #[cfg(feature = "parallel")]
+    pub fn par_evaluate_into(&self, scenarios: &[Scenario], targets: &mut [u32]) {
+        assert_eq!(scenarios.len(), targets.len());
+        scenarios
+            .par_iter()
+            .zip(targets.par_iter_mut())
+            .for_each(|(scenario, target)| *target = self.evaluate(scenario));
+    }
+
+    fn remaining(&self, balls: u16, wickets: u16) -> f32 {
+        assert!(wickets <= 10);
+        self.resources[balls as usize * WICKET_STATES + wickets as usize]
+    }
+
+    fn resource_loss(&self, int: &ScenarioInterruption) -> f32 {
+        let balls_left = int.overs_left.total_balls();
+        let balls_after = balls_left.saturating_sub(int.overs_lost.total_balls());
+        self.remaining(balls_left, int.wickets) - self.remaining(balls_after, int.wickets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::evaluator::{BatchEvaluator, Scenario, ScenarioInterruption};
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    fn interruption(
+        innings: Innings,
+        wickets: u16,
+        overs_left: Overs,
+        overs_lost: Overs,
+    ) -> ScenarioInterruption {
+        ScenarioInterruption {
+            innings,
+            wickets,
+            overs_left,
+            overs_lost,
+        }
+    }
+
+    #[test]
+    fn matches_icc_examples() {
+        let evaluator =
+            BatchEvaluator::new(&CricketMatch::new(Overs::new(50), Grade::ICCFullMember));
+        let example_one = [interruption(
+            Innings::First,
+            3,
+            Overs::new(30),
+            Overs::new(10),
+        )];
+        let example_four = [
+            interruption(Innings::Second, 1, Overs::new(38), Overs::new(10)),
+            interruption(Innings::Second, 3, Overs::new(18), Overs::new(2)),
+            interruption(
+                Innings::Second,
+                6,
+                7.4.try_into().unwrap(),
+                7.4.try_into().unwrap(),
+            ),
+        ];
+        let scenarios = [
+            Scenario {
+                first_innings_total: 180,
+                interruptions: &example_one,
+            },
+            Scenario {
+                first_innings_total: 250,
+                interruptions: &example_four,
+            },
+            Scenario {
+                first_innings_total: 250,
+                interruptions: &[],
+            },
+        ];
+        let mut targets = [0; 3];
+        evaluator.evaluate_into(&scenarios, &mut targets);
+
+        assert_eq!(targets, [185, 160, 0]);
+    }
+
+    #[test]
+    fn matches_cricket_match_for_shorter_games() {
+        let mut game = CricketMatch::new(Overs::new(45), Grade::ICCFullMember);
+        let evaluator = BatchEvaluator::new(&game);
+        let interruptions = [
+            interruption(Innings::First, 2, Overs::new(30), Overs::new(5)),
+            interruption(Innings::Second, 4, Overs::new(20), Overs::new(8)),
+        ];
+        for int in &interruptions {
+            game.interruption_with_runs(
+                int.wickets,
+                0,
+                int.overs_left.clone(),
+                int.overs_lost.clone(),
+                int.innings.clone(),
+            );
+        }
+        let scenario = Scenario {
+            first_innings_total: 230,
+            interruptions: &interruptions,
+        };
+
+        assert_eq!(evaluator.evaluate(&scenario), game.revised_target(230));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_more_than_ten_wickets() {
+        let evaluator =
+            BatchEvaluator::new(&CricketMatch::new(Overs::new(50), Grade::ICCFullMember));
+        let interruptions = [interruption(
+            Innings::Second,
+            11,
+            Overs::new(30),
+            Overs::new(10),
+        )];
+        evaluator.evaluate(&Scenario {
+            first_innings_total: 250,
+            interruptions: &interruptions,
+        });
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_evaluation_matches_sequential() {
+        let evaluator =
+            BatchEvaluator::new(&CricketMatch::new(Overs::new(50), Grade::ICCFullMember));
+        let interruptions: Vec<_> = (0..9)
+            .map(|wickets| {
+                [interruption(
+                    Innings::Second,
+                    wickets,
+                    Overs::new(30),
+                    Overs::new(10),
+                )]
+            })
+            .collect();
+        let scenarios: Vec<_> = interruptions
+            .iter()
+            .map(|interruptions| Scenario {
+                first_innings_total: 250,
+                interruptions,
+            })
+            .collect();
+        let mut sequential = vec![0; scenarios.len()];
+        let mut parallel = vec![0; scenarios.len()];
+        evaluator.evaluate_into(&scenarios, &mut sequential);
+        evaluator.par_evaluate_into(&scenarios, &mut parallel);
+
+        assert_eq!(sequential, parallel);
+    }
+}
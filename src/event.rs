@@ -0,0 +1,62 @@
+use std::time::SystemTime;
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::Innings;
+use crate::overs::Overs;
+
+/// Something that happened during a match. A `CricketMatch` is stored as an ordered log of these
+/// events, and everything else (interruptions, allocations, targets) is derived by replaying the
+/// log from the beginning. This means the state of the match can be recalculated as at any point
+/// in its history, which is useful for auditing a target after the fact.
+///
+/// Timestamps are optional as they aren't needed for any of the calculations, but are kept so
+/// that a log can be reconciled against a scorebook.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum MatchEvent {
+    /// An innings has started
+    InningsStart {
+        innings: Innings,
+        at: Option<SystemTime>,
+    },
+    /// Play has stopped. Wickets and runs are the totals for the innings at the time play stopped
+    /// (runs are None if they weren't recorded) and overs left are the overs remaining in the
+    /// innings before any deduction is made for this stoppage
+    Stoppage {
+        innings: Innings,
+        wickets: u16,
+        runs: Option<u16>,
+        overs_left: Overs,
+        at: Option<SystemTime>,
+    },
+    /// Play has been suspended at the most recent stoppage and will not resume until another day,
+    /// such as a reserve day. The stoppage stays open until play resumes
+    Suspension { at: Option<SystemTime> },
+    /// Play has resumed after the most recent stoppage, with overs lost deducted from the
+    /// innings that was stopped
+    Resumption {
+        overs_lost: Overs,
+        at: Option<SystemTime>,
+    },
+    /// The overs available to an innings have been reduced before that innings started, such as
+    /// a delayed start or a delay between innings. Reductions to an innings in progress should be
+    /// recorded as a stoppage and resumption instead
+    OversReduction { innings: Innings, overs_lost: Overs },
+    /// The overs available to an innings have been set directly before that innings started
+    Allocation { innings: Innings, overs: Overs },
+    /// An innings has finished. If the first innings finishes with overs remaining and wickets in
+    /// hand then it is treated as having been terminated, and the resources it had remaining
+    /// are lost
+    InningsEnd {
+        innings: Innings,
+        wickets: u16,
+        runs: u16,
+        overs_bowled: Overs,
+        at: Option<SystemTime>,
+    },
+    /// The match has been abandoned. If play was stopped at the time, all of the overs remaining
+    /// in that innings are lost
+    Abandonment { at: Option<SystemTime> },
+}
@@ -0,0 +1,194 @@
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::cricsheet::CricsheetMatch;
+use crate::table::DuckworthLewisTable;
+
+/// The range of decay rates searched when fitting each wicket
+const MIN_DECAY: f32 = 0.001;
+const MAX_DECAY: f32 = 0.3;
+const DECAY_STEP: f32 = 0.0005;
+
+/// The parameters of the exponential Duckworth Lewis model fitted to historical matches, as
+/// returned by `fit_resource_table`. The average runs still to come with u overs left and w
+/// wickets lost are modelled as Z(u, w) = Z0(w) * (1 - exp(-b(w) * u))
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct ResourceFit {
+    /// The asymptotic average runs still to come for each number of wickets lost
+    pub z_0: [f32; 10],
+    /// The decay rate for each number of wickets lost
+    pub b: [f32; 10],
+    /// The number of innings the fit was based on
+    pub innings: usize,
+}
+
+impl ResourceFit {
+    /// The average runs still to come with the given overs left and wickets lost. Overs left can
+    /// include part overs, e.g. 7.5 for 7 overs and 3 balls. The result is never more than the
+    /// runs still to come with fewer wickets lost
+    pub fn runs_to_come(&self, overs_left: f32, wickets_lost: u16) -> f32 {
+        (0..=wickets_lost.min(9) as usize)
+            .map(|w| self.z_0[w] * (1.0 - (-self.b[w] * overs_left).exp()))
+            .fold(f32::MAX, f32::min)
+            .max(0.0)
+    }
+
+    /// The fitted model as a resource table, with each entry a percentage of the runs still to
+    /// come at the start of a 50 over innings, rounded to one decimal place in the same way as
+    /// the standard edition table
+    pub fn table(&self) -> DuckworthLewisTable {
+        let full = self.runs_to_come(50.0, 0);
+        DuckworthLewisTable::from_fn(|balls_left, wickets_lost| {
+            let resources = self.runs_to_come(balls_left as f32 / 6.0, wickets_lost) / full;
+            (resources * 1000.0).round() / 10.0
+        })
+    }
+}
+
+/// Fits a resource table to the first innings of historical matches
+///
+/// The runs still to come are averaged for every combination of balls left and wickets lost
+/// seen in the data, and the exponential Duckworth Lewis form is then fitted to those averages
+/// separately for each number of wickets (weighted by how often each combination was seen).
+/// Wickets that never occur in the data use the fit for one fewer wickets lost
+///
+/// As with `estimate_g_50`, only the first innings of matches of no more than 50 overs that
+/// weren't decided by a rain rule are used. Returns None if there are no such innings
+pub fn fit_resource_table<'a>(
+    matches: impl IntoIterator<Item = &'a CricsheetMatch>,
+) -> Option<ResourceFit> {
+    let mut totals = vec![[(0.0, 0.0); 10]; 301];
+    let mut innings = 0;
+    for game in matches.into_iter().filter(|game| !game.rain_affected()) {
+        let Some(length) = game.length() else {
+            continue;
+        };
+        let Some(first) = game.regulation_innings().next() else {
+            continue;
+        };
+        let progress = first.progress();
+        let Some(total) = progress.last() else {
+            continue;
+        };
+        innings += 1;
+
+        let balls = length.total_balls();
+        let start = (balls, 0, total.runs);
+        let states = progress.iter().map(|p| {
+            (
+                balls.saturating_sub(p.balls),
+                p.wickets,
+                total.runs - p.runs,
+            )
+        });
+        for (balls_left, wickets, runs_to_come) in std::iter::once(start).chain(states) {
+            if balls_left > 0 && wickets < 10 {
+                let (sum, count) = &mut totals[balls_left as usize][wickets as usize];
+                *sum += runs_to_come as f32;
+                *count += 1.0;
+            }
+        }
+    }
+
+    if innings == 0 {
+        return None;
+    }
+
+    let mut z_0 = [0.0; 10];
+    let mut b = [0.0; 10];
+    for wickets in 0..10 {
+        let observations: Vec<_> = totals
+            .iter()
+            .enumerate()
+            .filter(|(_, by_wickets)| by_wickets[wickets].1 > 0.0)
+            .map(|(balls_left, by_wickets)| {
+                let (sum, count) = by_wickets[wickets];
+                (balls_left as f32 / 6.0, sum / count, count)
+            })
+            .collect();
+        (z_0[wickets], b[wickets]) = match fit_wicket(&observations) {
+            Some(fit) => fit,
+            None if wickets > 0 => (z_0[wickets - 1], b[wickets - 1]),
+            None => return None,
+        };
+    }
+
+    Some(ResourceFit { z_0, b, innings })
+}
+
+/// Fits Z0 and b to observations of (overs left, average runs to come, weight). For a given b
+/// the best Z0 can be found directly by weighted least squares, so b is found by searching
+fn fit_wicket(observations: &[(f32, f32, f32)]) -> Option<(f32, f32)> {
+    let steps = ((MAX_DECAY - MIN_DECAY) / DECAY_STEP) as usize;
+    (0..=steps)
+        .map(|step| MIN_DECAY + step as f32 * DECAY_STEP)
+        .filter_map(|b| {
+            let (numerator, denominator) = observations.iter().fold(
+                (0.0, 0.0),
+                |(numerator, denominator), (overs, runs, weight)| {
+                    let shape = 1.0 - (-b * overs).exp();
+                    (
+                        numerator + weight * runs * shape,
+                        denominator + weight * shape * shape,
+                    )
+                },
+            );
+            if denominator <= 0.0 {
+                return None;
+            }
+            let z_0 = numerator / denominator;
+            let error: f32 = observations
+                .iter()
+                .map(|(overs, runs, weight)| {
+                    weight * (runs - z_0 * (1.0 - (-b * overs).exp())).powi(2)
+                })
+                .sum();
+            Some((z_0, b, error))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .map(|(z_0, b, _)| (z_0, b))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cricsheet::test::SAMPLE;
+    use crate::cricsheet::CricsheetMatch;
+    use crate::fitting::{fit_resource_table, fit_wicket};
+    use crate::Overs;
+
+    #[test]
+    fn recovers_exponential_parameters() {
+        let observations: Vec<_> = (1..=50)
+            .map(|overs| {
+                let overs = overs as f32;
+                (overs, 280.0 * (1.0 - (-0.035 * overs).exp()), 1.0)
+            })
+            .collect();
+        let (z_0, b) = fit_wicket(&observations).unwrap();
+
+        assert!((z_0 - 280.0).abs() < 2.0);
+        assert!((b - 0.035).abs() < 0.001);
+    }
+
+    #[test]
+    fn fitted_table_decreases_with_wickets() {
+        let game = CricsheetMatch::from_reader(SAMPLE.as_bytes()).unwrap();
+        let fit = fit_resource_table([&game]).unwrap();
+        let table = fit.table();
+
+        assert_eq!(fit.innings, 1);
+        assert_eq!(table.resources_remaining(&Overs::new(50), 0), 100.0);
+        for wickets in 1..10 {
+            assert!(
+                table.resources_remaining(&Overs::new(30), wickets)
+                    <= table.resources_remaining(&Overs::new(30), wickets - 1)
+            );
+        }
+    }
+
+    #[test]
+    fn no_innings_gives_no_fit() {
+        assert!(fit_resource_table([]).is_none());
+    }
+}
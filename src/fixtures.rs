@@ -0,0 +1,214 @@
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Calculation, CricketMatch, Formula, Grade, Innings};
+use crate::overs::Overs;
+use crate::DuckworthLewisError;
+
+/// A row of a fixtures CSV, as read by `recompute_fixtures`. Either a grade (using the same names
+/// as the cli, e.g. icc-full-member) or a G50 must be given; if both are, G50 is used
+///
+/// Interruptions are separated by semicolons, with each one written as
+/// innings:wickets:runs:overs_left:overs_lost, e.g. `first:3:95:30:10;second:1:20:35:5`. Fields
+/// have the same meaning as the arguments to `CricketMatch::interruption`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    pub length: String,
+    pub grade: Option<String>,
+    pub g_50: Option<u16>,
+    pub first_innings_total: usize,
+    pub interruptions: Option<String>,
+}
+
+/// A row of the CSV written by `recompute_fixtures`. Calculated fields are blank if the row
+/// couldn't be calculated, in which case error explains why
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FixtureResult {
+    /// The position of the fixture in the input, starting from 1
+    pub row: usize,
+    pub par: Option<u32>,
+    pub target: Option<u32>,
+    pub team_1_resources: Option<String>,
+    pub team_2_resources: Option<String>,
+    pub team_2_overs: Option<String>,
+    pub formula: Option<Formula>,
+    pub error: Option<String>,
+}
+
+/// The number of fixtures read by `recompute_fixtures` and how many of them couldn't be
+/// calculated
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FixtureSummary {
+    pub rows: usize,
+    pub errors: usize,
+}
+
+impl Fixture {
+    /// Builds the match described by this fixture and breaks down its target calculation
+    pub fn calculate(&self) -> Result<Calculation, DuckworthLewisError> {
+        let length: Overs = self.length.parse()?;
+        if length > Overs::new(50) || length == Overs::new(0) {
+            return Err(DuckworthLewisError::InvalidFixture(format!(
+                "length must be more than 0 and no more than 50 overs, got {}",
+                length
+            )));
+        }
+        let mut game = match (self.g_50, &self.grade) {
+            (Some(g_50), _) => CricketMatch::new_with_g_50(length, g_50),
+            (None, Some(grade)) => CricketMatch::new(length, grade.parse::<Grade>()?),
+            (None, None) => {
+                return Err(DuckworthLewisError::InvalidFixture(
+                    "either grade or g_50 is required".to_string(),
+                ))
+            }
+        };
+
+        let interruptions = self.interruptions.as_deref().unwrap_or_default();
+        for interruption in interruptions
+            .split(';')
+            .filter(|int| !int.trim().is_empty())
+        {
+            let (innings, wickets, runs, overs_left, overs_lost) =
+                parse_interruption(interruption)?;
+            if wickets >= 10 {
+                return Err(DuckworthLewisError::InvalidFixture(format!(
+                    "wickets must be less than 10 in {}",
+                    interruption
+                )));
+            }
+            if overs_left > game.allocation(&innings) || overs_lost > overs_left {
+                return Err(DuckworthLewisError::InvalidFixture(format!(
+                    "overs left must be within the innings's allocation and overs lost within overs left in {}",
+                    interruption
+                )));
+            }
+            game.interruption_with_runs(wickets, runs, overs_left, overs_lost, innings);
+        }
+
+        Ok(game.calculation(self.first_innings_total))
+    }
+}
+
+/// Recomputes the target for every fixture in a CSV, writing a CSV with the par, target and a
+/// breakdown of the calculation for each one. The input must have a header row naming the
+/// columns in `Fixture`
+///
+/// A fixture that can't be read or calculated doesn't stop the batch; its error is written to
+/// the output in place of the calculation. An error is only returned if the input can't be read
+/// as CSV at all or the output can't be written
+pub fn recompute_fixtures(
+    reader: impl Read,
+    writer: impl Write,
+) -> Result<FixtureSummary, DuckworthLewisError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let mut writer = csv::Writer::from_writer(writer);
+    let mut summary = FixtureSummary { rows: 0, errors: 0 };
+
+    for (index, fixture) in reader.deserialize::<Fixture>().enumerate() {
+        let calculation = match fixture {
+            Err(err) if matches!(err.kind(), csv::ErrorKind::Io(_)) => {
+                return Err(DuckworthLewisError::InvalidCsv(err.to_string()))
+            }
+            Err(err) => Err(DuckworthLewisError::InvalidFixture(err.to_string())),
+            Ok(fixture) => fixture.calculate(),
+        };
+        let row = index + 1;
+        let result = match calculation {
+            Ok(calculation) => FixtureResult {
+                row,
+                par: Some(calculation.par as u32),
+                target: Some(calculation.target),
+                team_1_resources: Some(format!("{:.1}", calculation.team_1_resources)),
+                team_2_resources: Some(format!("{:.1}", calculation.team_2_resources)),
+                team_2_overs: Some(calculation.team_2_overs.to_string()),
+                formula: Some(calculation.formula),
+                error: None,
+            },
+            Err(err) => FixtureResult {
+                row,
+                error: Some(err.to_string()),
+                ..FixtureResult::default()
+            },
+        };
+
+        summary.rows += 1;
+        if result.error.is_some() {
+            summary.errors += 1;
+        }
+        writer
+            .serialize(result)
+            .map_err(|err| DuckworthLewisError::InvalidCsv(err.to_string()))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|err| DuckworthLewisError::InvalidCsv(err.to_string()))?;
+    Ok(summary)
+}
+
+fn parse_interruption(
+    interruption: &str,
+) -> Result<(Innings, u16, u16, Overs, Overs), DuckworthLewisError> {
+    let fields: Vec<_> = interruption.trim().split(':').collect();
+    match fields[..] {
+        [innings, wickets, runs, overs_left, overs_lost] => Ok((
+            innings.parse()?,
+            wickets.trim().parse()?,
+            runs.trim().parse()?,
+            overs_left.trim().parse()?,
+            overs_lost.trim().parse()?,
+        )),
+        _ => Err(DuckworthLewisError::InvalidFixture(format!(
+            "interruptions must be innings:wickets:runs:overs_left:overs_lost, got {}",
+            interruption
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::{recompute_fixtures, FixtureSummary};
+
+    #[test]
+    fn recomputes_each_row() {
+        let input = "length,grade,g_50,first_innings_total,interruptions
+50,icc-full-member,,180,first:3:95:30:10
+50,,245,250,second:1:55:38:10
+50,icc-full-member,,250,
+";
+        let mut output = Vec::new();
+        let summary = recompute_fixtures(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let rows: Vec<_> = output.lines().collect();
+
+        assert_eq!(summary, FixtureSummary { rows: 3, errors: 0 });
+        assert_eq!(
+            rows[0],
+            "row,par,target,team_1_resources,team_2_resources,team_2_overs,formula,error"
+        );
+        assert!(rows[1].starts_with("1,184,185,"));
+        assert!(rows[2].starts_with("2,217,218,"));
+        assert_eq!(rows[3], "3,250,251,100.0,100.0,50,Unchanged,");
+    }
+
+    #[test]
+    fn reports_errors_without_stopping() {
+        let input = "length,grade,g_50,first_innings_total,interruptions
+50,county,,180,
+55,,200,180,
+50,,200,abc,
+50,,200,180,first:10:95:30:10
+50,,200,180,first:3:95
+50,,200,180,
+";
+        let mut output = Vec::new();
+        let summary = recompute_fixtures(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let rows: Vec<_> = output.lines().collect();
+
+        assert_eq!(summary, FixtureSummary { rows: 6, errors: 5 });
+        assert!(rows[1].ends_with("unknown grade county"));
+        assert!(rows[6].starts_with("6,180,181,"));
+    }
+}
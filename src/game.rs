@@ -1,15 +1,21 @@
 #[cfg(feature = "cli")]
 use clap::ValueEnum;
 use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
 #[cfg(feature = "ser")]
 use serde::{Deserialize, Serialize};
 
+use crate::event::MatchEvent;
 use crate::overs::Overs;
-use crate::table::DUCKWORTH_LEWIS_TABLE;
+use crate::table::{DuckworthLewisTable, DUCKWORTH_LEWIS_TABLE};
+use crate::DuckworthLewisError;
 
 const G50_FULL: f32 = 245.0;
 const G50_OTHER: f32 = 200.0;
+/// First innings totals more than this multiple of G50 are considered well above it
+const DEFAULT_DIVERGENCE_THRESHOLD: f32 = 1.2;
 
 /// The relevant Grade of the teams involved in this match. Note that this is the teams grade not
 /// the match grade. That means that the the grade is the highest level that that team is eligible
@@ -46,24 +52,370 @@ pub enum Innings {
     Second,
 }
 
-/// Representation of a cricket match, used to hold the basic details of the match and the details
-/// of any interruptions that have occurred during the match
+impl FromStr for Grade {
+    type Err = DuckworthLewisError;
+
+    /// Parses the same names the cli accepts, e.g. "icc-full-member"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "icc-full-member" => Ok(Grade::ICCFullMember),
+            "first-class" => Ok(Grade::FirstClass),
+            "u19-international" => Ok(Grade::U19International),
+            "u15-international" => Ok(Grade::U15International),
+            "womens-international" => Ok(Grade::WomensInternational),
+            "icc-associate-member" => Ok(Grade::ICCAssociateMember),
+            _ => Err(DuckworthLewisError::UnknownGrade(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Innings {
+    type Err = DuckworthLewisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "first" => Ok(Innings::First),
+            "second" => Ok(Innings::Second),
+            _ => Err(DuckworthLewisError::UnknownInnings(s.to_string())),
+        }
+    }
+}
+
+/// Where a match is up to, as derived from its event log
+#[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum MatchStatus {
+    NotStarted,
+    InProgress(Innings),
+    Stopped(Innings),
+    /// Play was stopped and will resume on another day
+    Suspended(Innings),
+    InningsBreak,
+    Completed,
+    Abandoned,
+}
+
+impl Display for MatchStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let innings = |innings: &Innings| match innings {
+            Innings::First => "first",
+            Innings::Second => "second",
+        };
+        match self {
+            MatchStatus::NotStarted => write!(f, "not started"),
+            MatchStatus::InProgress(i) => write!(f, "{} innings in progress", innings(i)),
+            MatchStatus::Stopped(i) => write!(f, "{} innings stopped", innings(i)),
+            MatchStatus::Suspended(i) => write!(f, "{} innings suspended", innings(i)),
+            MatchStatus::InningsBreak => write!(f, "innings break"),
+            MatchStatus::Completed => write!(f, "completed"),
+            MatchStatus::Abandoned => write!(f, "abandoned"),
+        }
+    }
+}
+
+/// Representation of a cricket match, held as an ordered log of the events that have occurred
+/// during the match. Interruptions, allocations and targets are all derived from the log, so
+/// the match can be replayed and recalculated as at any point in its history
+#[derive(Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "ser", serde(from = "StoredMatch"))]
 pub struct CricketMatch {
     length: Overs,
     g_50: f32,
+    events: Vec<MatchEvent>,
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    table: Option<Box<DuckworthLewisTable>>,
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    divergence_threshold: Option<f32>,
+}
+
+/// The serialised form of a match. Older versions of this crate stored a flat list of
+/// interruptions rather than an event log, so these are converted when they are read
+#[cfg(feature = "ser")]
+#[derive(Deserialize)]
+struct StoredMatch {
+    length: Overs,
+    g_50: f32,
+    #[serde(default)]
+    events: Vec<MatchEvent>,
+    #[serde(default)]
     interruptions: Vec<Interruption>,
+    #[serde(default)]
+    table: Option<Box<DuckworthLewisTable>>,
+    #[serde(default)]
+    divergence_threshold: Option<f32>,
 }
 
-#[derive(Debug)]
-#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+#[cfg(feature = "ser")]
+impl From<StoredMatch> for CricketMatch {
+    fn from(stored: StoredMatch) -> Self {
+        let mut events = stored.events;
+        for int in stored.interruptions {
+            events.push(MatchEvent::Stoppage {
+                innings: int.innings,
+                wickets: int.wickets,
+                runs: int.runs,
+                overs_left: int.overs_left,
+                at: None,
+            });
+            events.push(MatchEvent::Resumption {
+                overs_lost: int.overs_lost,
+                at: None,
+            });
+        }
+        CricketMatch {
+            length: stored.length,
+            g_50: stored.g_50,
+            events,
+            table: stored.table,
+            divergence_threshold: stored.divergence_threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Deserialize))]
 struct Interruption {
     wickets: u16,
+    #[cfg_attr(feature = "ser", serde(default))]
+    runs: Option<u16>,
     overs_left: Overs,
     overs_lost: Overs,
     innings: Innings,
 }
 
+/// The par score and target given to the team batting second after an interruption to their
+/// innings, as reported by `CricketMatch::target_history`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct TargetRevision {
+    /// The position of the interruption in `CricketMatch::interruption_summaries`
+    pub interruption: usize,
+    /// The overs allocated to the second innings once the interruption was allowed for
+    pub overs: Overs,
+    pub par: u32,
+    pub target: u32,
+}
+
+/// Which of the Standard Edition formulae was used to calculate par, which depends on how the
+/// resources available to the two teams compare
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum Formula {
+    /// Team 2 had fewer resources, so par is the first innings total scaled by R2 / R1
+    Scaled,
+    /// Team 2 had more resources, so par is the first innings total plus G50 × (R2 - R1) / 100
+    Increased,
+    /// Both teams had the same resources, so par is the first innings total
+    Unchanged,
+}
+
+impl Display for Formula {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Formula::Scaled => "scaled",
+            Formula::Increased => "increased",
+            Formula::Unchanged => "unchanged",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The resources lost to a single interruption, as reported in a `Calculation`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct ResourceLoss {
+    pub innings: Innings,
+    pub wickets: u16,
+    pub overs_left: Overs,
+    pub overs_lost: Overs,
+    /// The resources remaining when play was suspended
+    pub at_suspension: f32,
+    /// The resources remaining when play resumed, after the lost overs were deducted
+    pub at_resumption: f32,
+}
+
+impl ResourceLoss {
+    /// The resources the innings lost to this interruption
+    pub fn lost(&self) -> f32 {
+        self.at_suspension - self.at_resumption
+    }
+}
+
+impl Display for ResourceLoss {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let wickets = match self.wickets {
+            1 => "1 wicket".to_string(),
+            wickets => format!("{} wickets", wickets),
+        };
+        write!(
+            f,
+            "Interrupted with {} overs left and {} down, {} overs lost: {:.1}% at suspension, {:.1}% at resumption, {:.1}% lost",
+            self.overs_left,
+            wickets,
+            self.overs_lost,
+            self.at_suspension,
+            self.at_resumption,
+            self.lost()
+        )
+    }
+}
+
+/// Every step of a target calculation, as returned by `CricketMatch::calculation`. This follows
+/// the layout of the worked examples in the ICC's Duckworth Lewis methodology document
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Calculation {
+    pub g_50: f32,
+    pub first_innings_total: usize,
+    /// The overs and resources available to team 1 at the start of their innings
+    pub team_1_overs: Overs,
+    pub team_1_starting_resources: f32,
+    /// The overs and resources available to team 2 at the start of their innings
+    pub team_2_overs: Overs,
+    pub team_2_starting_resources: f32,
+    /// Each interruption in the order it was recorded
+    pub interruptions: Vec<ResourceLoss>,
+    /// The resources available to each team once every interruption is allowed for
+    pub team_1_resources: f32,
+    pub team_2_resources: f32,
+    pub formula: Formula,
+    /// Par before rounding down
+    pub par: f32,
+    pub target: u32,
+}
+
+impl Display for Calculation {
+    /// Sets the calculation out step by step, in the order of the worked examples in the ICC's
+    /// methodology document
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "G50 = {}", self.g_50)?;
+        let innings = [
+            (
+                Innings::First,
+                "Team 1",
+                &self.team_1_overs,
+                self.team_1_starting_resources,
+                self.team_1_resources,
+            ),
+            (
+                Innings::Second,
+                "Team 2",
+                &self.team_2_overs,
+                self.team_2_starting_resources,
+                self.team_2_resources,
+            ),
+        ];
+        for (innings, team, overs, starting, total) in innings {
+            writeln!(
+                f,
+                "{} starts with {} overs: resources available {:.1}%",
+                team, overs, starting
+            )?;
+            for int in self
+                .interruptions
+                .iter()
+                .filter(|int| int.innings == innings)
+            {
+                writeln!(f, "  {}", int)?;
+            }
+            writeln!(f, "{} resources: {:.1}%", team, total)?;
+        }
+
+        let (s, r1, r2) = (
+            self.first_innings_total,
+            self.team_1_resources,
+            self.team_2_resources,
+        );
+        match self.formula {
+            Formula::Scaled => writeln!(
+                f,
+                "R2 < R1, so par = S × R2 / R1 = {} × {:.1} / {:.1} = {:.2}",
+                s, r2, r1, self.par
+            ),
+            Formula::Increased => writeln!(
+                f,
+                "R2 > R1, so par = S + G50 × (R2 - R1) / 100 = {} + {} × ({:.1} - {:.1}) / 100 = {:.2}",
+                s, self.g_50, r2, r1, self.par
+            ),
+            Formula::Unchanged => writeln!(f, "R2 = R1, so par = S = {}", s),
+        }?;
+        write!(f, "Target = {}", self.target)
+    }
+}
+
+/// The result of replaying a match's event log
+struct State<'a> {
+    table: &'a DuckworthLewisTable,
+    first_innings_overs: Overs,
+    team_2_overs: Option<Overs>,
+    interruptions: Vec<Interruption>,
+    stoppage: Option<Interruption>,
+    status: MatchStatus,
+}
+
+/// The state of an innings at a point in time. Wickets and runs are totals for the innings and
+/// overs left are the overs remaining in the innings's current allocation
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Situation {
+    pub innings: Innings,
+    pub wickets: u16,
+    pub runs: u16,
+    pub overs_left: Overs,
+}
+
+/// A snapshot of the match at the moment an interruption occurred, as reported by
+/// `CricketMatch::interruption_summaries`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct InterruptionSummary {
+    pub innings: Innings,
+    pub wickets: u16,
+    /// The runs scored when play stopped, if they were recorded
+    pub runs: Option<u16>,
+    pub overs_faced: Overs,
+    pub overs_lost: Overs,
+    /// The par score at the moment of the interruption. Only available in the second innings
+    pub par: Option<u32>,
+}
+
+impl InterruptionSummary {
+    /// Whether the batting side was ahead of par when play stopped. Being level with par
+    /// is not considered to be ahead. Always None for first innings interruptions, and for
+    /// interruptions recorded without the runs scored
+    pub fn ahead(&self) -> Option<bool> {
+        self.par.zip(self.runs).map(|(par, runs)| runs as u32 > par)
+    }
+}
+
+impl Display for InterruptionSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let innings = match self.innings {
+            Innings::First => "First",
+            Innings::Second => "Second",
+        };
+        let score = match self.runs {
+            Some(runs) => format!("at {}/{}", runs, self.wickets),
+            None => format!("with {} down", self.wickets),
+        };
+        write!(
+            f,
+            "{} innings interrupted after {} overs {}, {} overs lost",
+            innings, self.overs_faced, score, self.overs_lost
+        )?;
+        match (self.par, self.runs.map(|runs| runs as u32)) {
+            (Some(par), Some(runs)) => match runs.cmp(&par) {
+                Ordering::Greater => write!(f, "; par {}, ahead by {}", par, runs - par),
+                Ordering::Less => write!(f, "; par {}, behind by {}", par, par - runs),
+                Ordering::Equal => write!(f, "; par {}, level", par),
+            },
+            (Some(par), None) => write!(f, "; par {}", par),
+            (None, _) => Ok(()),
+        }
+    }
+}
+
 impl CricketMatch {
     /// Create a new cricket match at a specified grade
     ///
@@ -80,11 +432,13 @@ impl CricketMatch {
             Grade::ICCFullMember | Grade::FirstClass => G50_FULL,
             _ => G50_OTHER,
         };
-        let interruptions = Vec::new();
+        let events = Vec::new();
         CricketMatch {
             length,
             g_50,
-            interruptions,
+            events,
+            table: None,
+            divergence_threshold: None,
         }
     }
 
@@ -95,19 +449,107 @@ impl CricketMatch {
     pub fn new_with_g_50(length: Overs, g_50: u16) -> CricketMatch {
         assert!(length.overs <= 50);
         let g_50 = g_50 as f32;
-        let interruptions = Vec::new();
+        let events = Vec::new();
         CricketMatch {
             length,
             g_50,
-            interruptions,
+            events,
+            table: None,
+            divergence_threshold: None,
         }
     }
 
-    /// Record an interruption has occurred. Wickets are total wickets lost in the innings,
-    /// runs are total runs scored in the innings. Overs left are as at the beginning of
-    /// the stoppage (i.e. not factoring in any adjustment because of this stoppage, but
-    /// allowing for previous stoppages). Overs lost are the overs lost for this innings
-    /// (i.e. if 20 overs total are lost, split as 10 overs per innings, over_lost = 10)
+    /// Use a custom resource table for this match instead of the standard edition table, such as
+    /// one produced by `fit_resource_table`
+    pub fn with_table(mut self, table: DuckworthLewisTable) -> CricketMatch {
+        self.table = Some(Box::new(table));
+        self
+    }
+
+    /// Warn that the standard edition target may be unfair once the first innings total is more
+    /// than the given multiple of G50, instead of the default. See `divergence_warning`
+    pub fn with_divergence_threshold(mut self, threshold: f32) -> CricketMatch {
+        self.divergence_threshold = Some(threshold);
+        self
+    }
+
+    /// The multiple of G50 above which a first innings total triggers a `DivergenceWarning`
+    pub fn divergence_threshold(&self) -> f32 {
+        self.divergence_threshold
+            .unwrap_or(DEFAULT_DIVERGENCE_THRESHOLD)
+    }
+
+    /// The resource table used for this match
+    pub fn table(&self) -> &DuckworthLewisTable {
+        self.table.as_deref().unwrap_or(&DUCKWORTH_LEWIS_TABLE)
+    }
+
+    /// Append an event to the match log
+    ///
+    /// Panics
+    /// Wickets must be less than 10 at a stoppage and no more than 10 at the end of an innings
+    /// Overs left at a stoppage must be less than or equal to the total length of the innings
+    /// Overs bowled at the end of an innings must not exceed the overs allocated to it
+    /// A resumption or suspension must follow a stoppage
+    pub fn record(&mut self, event: MatchEvent) {
+        match &event {
+            MatchEvent::Stoppage {
+                wickets,
+                overs_left,
+                ..
+            } => {
+                assert!(*wickets < 10);
+                assert!(overs_left <= &self.length);
+            }
+            MatchEvent::Resumption { .. } | MatchEvent::Suspension { .. } => {
+                assert!(self.state().stoppage.is_some())
+            }
+            MatchEvent::Allocation { overs, .. } => assert!(overs <= &self.length),
+            MatchEvent::InningsEnd {
+                innings,
+                wickets,
+                overs_bowled,
+                ..
+            } => {
+                assert!(*wickets <= 10);
+                assert!(overs_bowled <= &self.allocation(innings));
+            }
+            _ => {}
+        }
+        self.events.push(event);
+    }
+
+    /// The events recorded for this match, in the order they occurred
+    pub fn events(&self) -> &[MatchEvent] {
+        &self.events
+    }
+
+    /// Replays the first `event_count` events of the log, returning the match as it stood at
+    /// that point. Targets and summaries calculated from the result are those that would have
+    /// been given at the time
+    pub fn as_at(&self, event_count: usize) -> CricketMatch {
+        CricketMatch {
+            length: self.length.clone(),
+            g_50: self.g_50,
+            events: self.events.iter().take(event_count).cloned().collect(),
+            table: self.table.clone(),
+            divergence_threshold: self.divergence_threshold,
+        }
+    }
+
+    /// Where the match is currently up to
+    pub fn status(&self) -> MatchStatus {
+        self.state().status
+    }
+
+    /// Record an interruption has occurred. Wickets are total wickets lost in the innings.
+    /// Overs left are as at the beginning of the stoppage (i.e. not factoring in any adjustment
+    /// because of this stoppage, but allowing for previous stoppages). Overs lost are the overs
+    /// lost for this innings (i.e. if 20 overs total are lost, split as 10 overs per innings,
+    /// over_lost = 10)
+    ///
+    /// The runs scored when play stopped aren't recorded, so `interruption_summaries` can't say
+    /// whether the batting side was ahead of par. Use `interruption_with_runs` if they are known
     ///
     /// Panics
     /// Wickets must less than 10
@@ -120,25 +562,188 @@ impl CricketMatch {
         overs_lost: Overs,
         innings: Innings,
     ) {
-        assert!(wickets < 10);
-        assert!(overs_left <= self.length);
-        self.interruptions.push(Interruption {
+        self.record_interruption(wickets, None, overs_left, overs_lost, innings);
+    }
+
+    /// Record an interruption has occurred, along with the runs scored. Wickets are total wickets
+    /// lost in the innings, runs are total runs scored in the innings when play stopped. Overs
+    /// left and overs lost are as for `interruption`
+    ///
+    /// This records a stoppage immediately followed by a resumption. Use `record` directly if
+    /// the times play stopped and resumed are needed
+    ///
+    /// Panics
+    /// Wickets must less than 10
+    /// Overs left must be less than or equal to the total length of the innings (e.g. 50)
+    ///
+    pub fn interruption_with_runs(
+        &mut self,
+        wickets: u16,
+        runs: u16,
+        overs_left: Overs,
+        overs_lost: Overs,
+        innings: Innings,
+    ) {
+        self.record_interruption(wickets, Some(runs), overs_left, overs_lost, innings);
+    }
+
+    /// Records a stoppage immediately followed by a resumption
+    fn record_interruption(
+        &mut self,
+        wickets: u16,
+        runs: Option<u16>,
+        overs_left: Overs,
+        overs_lost: Overs,
+        innings: Innings,
+    ) {
+        self.record(MatchEvent::Stoppage {
+            innings,
             wickets,
+            runs,
             overs_left,
+            at: None,
+        });
+        self.record(MatchEvent::Resumption {
             overs_lost,
-            innings,
+            at: None,
         });
     }
 
+    /// Record that play has been suspended in the given situation and will continue on another
+    /// day, such as a reserve day. The match stays stopped until `resume` is called, and can
+    /// still be abandoned in the meantime. To suspend play before an innings has started, give
+    /// no wickets or runs and the overs currently allocated to the innings
+    ///
+    /// Panics
+    /// Wickets must less than 10
+    /// Overs left must be less than or equal to the total length of the innings (e.g. 50)
+    pub fn suspend(&mut self, situation: &Situation) {
+        self.record(MatchEvent::Stoppage {
+            innings: situation.innings.clone(),
+            wickets: situation.wickets,
+            runs: Some(situation.runs),
+            overs_left: situation.overs_left.clone(),
+            at: None,
+        });
+        self.record(MatchEvent::Suspension { at: None });
+    }
+
+    /// Resume a suspended match from where it was suspended, with the given overs left in the
+    /// suspended innings. Any overs that are no longer available are deducted from the suspended
+    /// innings as if play had been interrupted, so targets and history stay consistent with an
+    /// interruption on the same day. If the first innings was suspended, the team batting second
+    /// will be allocated the same overs as the first innings unless changed
+    ///
+    /// Panics
+    /// The match must be suspended
+    /// Overs left must not be more than the overs left when play was suspended
+    pub fn resume(&mut self, overs_left: Overs) {
+        let state = self.state();
+        assert!(matches!(state.status, MatchStatus::Suspended(_)));
+        let suspended = state.stoppage.expect("a suspended match has a stoppage");
+        assert!(overs_left <= suspended.overs_left);
+        self.record(MatchEvent::Resumption {
+            overs_lost: suspended.overs_left - &overs_left,
+            at: None,
+        });
+    }
+
+    /// Record that the first innings was terminated and not resumed, for example because rain
+    /// arrived late in the innings and there was only time for the second innings to be played.
+    /// Overs bowled are the overs completed by the team batting first when play stopped
+    ///
+    /// Unless changed by `delay_between_innings` or `set_team_2_overs`, the team batting second
+    /// will be allocated the same number of overs as were bowled in the first innings
+    ///
+    /// Panics
+    /// Wickets must less than 10
+    /// Overs bowled must be less than or equal to the overs allocated to the first innings
+    pub fn terminate_first_innings(&mut self, wickets: u16, runs: u16, overs_bowled: Overs) {
+        assert!(wickets < 10);
+        self.record(MatchEvent::InningsEnd {
+            innings: Innings::First,
+            wickets,
+            runs,
+            overs_bowled,
+            at: None,
+        });
+    }
+
+    /// Record a delay between the innings that reduces the overs available to the team batting
+    /// second. Overs lost are deducted from the allocation the second innings would otherwise
+    /// have started with
+    pub fn delay_between_innings(&mut self, overs_lost: Overs) {
+        self.record(MatchEvent::OversReduction {
+            innings: Innings::Second,
+            overs_lost,
+        });
+    }
+
+    /// Set the overs available to the team batting second at the start of their innings,
+    /// replacing the allocation derived from the first innings
+    ///
+    /// Panics
+    /// Overs must be less than or equal to the total length of the innings (e.g. 50)
+    pub fn set_team_2_overs(&mut self, overs: Overs) {
+        self.record(MatchEvent::Allocation {
+            innings: Innings::Second,
+            overs,
+        });
+    }
+
+    /// Record that the match has been abandoned. If play is currently stopped then all of the
+    /// overs remaining in the stopped innings are lost, and `revised_target` will return the
+    /// score the team batting second needed to have reached to win the match
+    pub fn abandon(&mut self) {
+        self.record(MatchEvent::Abandonment { at: None });
+    }
+
+    /// The scheduled length of each innings before any interruptions
+    pub fn length(&self) -> &Overs {
+        &self.length
+    }
+
+    /// The G50 value used for this match - i.e. the average score expected from an uninterrupted
+    /// 50 over innings
+    pub fn g_50(&self) -> f32 {
+        self.g_50
+    }
+
+    /// The percentage of resources available to an innings in total, allowing for every
+    /// interruption that has been recorded against it so far
+    pub fn resources(&self, innings: &Innings) -> f32 {
+        let (t1_resources, t2_resources) = self.state().resources();
+        match innings {
+            Innings::First => t1_resources,
+            Innings::Second => t2_resources,
+        }
+    }
+
+    /// The overs currently allocated to an innings, allowing for every reduction that has been
+    /// recorded against it so far
+    pub fn allocation(&self, innings: &Innings) -> Overs {
+        let state = self.state();
+        let initial = match innings {
+            Innings::First => state.first_innings_overs.clone(),
+            Innings::Second => state.team_2_initial_overs(),
+        };
+        state
+            .interruptions
+            .iter()
+            .filter(|int| &int.innings == innings)
+            .fold(initial, |overs, int| overs - &int.overs_lost)
+    }
+
     /// Returns the current target that the team batting second needs to have achieved
     /// at the conclusion of their innings. Assumes that innings 1 has been fully
-    /// completed and all interruptions have been entered. If no interruptions
-    /// have been entered, this will return 0
+    /// completed and all interruptions have been entered. If no interruptions have been
+    /// entered and team 2's allocation hasn't been changed, this will return 0
     ///
     /// If match has been abandoned after the second innings has completed the required
-    /// minimum, this should be entered as an interruption and then revised_total will
-    /// return the target total that the team batting second should have achieved at the
-    /// point the match was abandoned in order to have won the match.
+    /// minimum, this should be entered as an interruption (or a stoppage followed by an
+    /// abandonment) and then revised_total will return the target total that the team
+    /// batting second should have achieved at the point the match was abandoned in order
+    /// to have won the match.
     ///
     /// This method does not alter internal state, so it can be called many times, including
     /// as a recalculation of the required total if additional interruptions occurred.
@@ -146,61 +751,356 @@ impl CricketMatch {
     /// This is a Mark Boucher friendly total - i.e. we're calculating the target not the par
     /// score
     pub fn revised_target(&self, first_innings_total: usize) -> u32 {
-        if self.interruptions.is_empty() {
+        let state = self.state();
+        if state.interruptions.is_empty() && state.team_2_overs.is_none() {
             return 0;
         }
 
-        let (t1_resources, total_overs) = self
+        let (t1_resources, t2_resources) = state.resources();
+        target(self.g_50, first_innings_total, t1_resources, t2_resources)
+    }
+
+    /// Returns the current par score for the team batting second - i.e. the score that would
+    /// tie the match. This is always one less than the target. As with `revised_target`, this
+    /// will return 0
+    /// if no interruptions have been entered and team 2's allocation hasn't been changed
+    pub fn revised_par(&self, first_innings_total: usize) -> u32 {
+        let state = self.state();
+        if state.interruptions.is_empty() && state.team_2_overs.is_none() {
+            return 0;
+        }
+
+        let (t1_resources, t2_resources) = state.resources();
+        par(self.g_50, first_innings_total, t1_resources, t2_resources) as u32
+    }
+
+    /// Returns the par score and target given to the team batting second after each interruption
+    /// to their innings, in the order the interruptions occurred. The final revision is the same
+    /// as the current `revised_par` and `revised_target`
+    pub fn target_history(&self, first_innings_total: usize) -> Vec<TargetRevision> {
+        let state = self.state();
+        let (t1_resources, _) = state.team_1_resources();
+        let mut overs = state.team_2_initial_overs();
+        let mut t2_resources = state.table.resources_remaining(&overs, 0);
+
+        state
             .interruptions
+            .iter()
+            .enumerate()
+            .filter(|(_, int)| int.innings == Innings::Second)
+            .map(|(interruption, int)| {
+                overs = &overs - &int.overs_lost;
+                t2_resources -= int.resource_loss(state.table);
+                TargetRevision {
+                    interruption,
+                    overs: overs.clone(),
+                    par: par(self.g_50, first_innings_total, t1_resources, t2_resources) as u32,
+                    target: target(self.g_50, first_innings_total, t1_resources, t2_resources),
+                }
+            })
+            .collect()
+    }
+
+    /// Summarises each recorded interruption in the order they were entered, including the par
+    /// score for the team batting second at the moment play was stopped
+    ///
+    /// Par scores are only reported for interruptions in the second innings, as there is nothing
+    /// for the team batting first to be ahead or behind of
+    pub fn interruption_summaries(&self, first_innings_total: usize) -> Vec<InterruptionSummary> {
+        let state = self.state();
+        let (t1_resources, _) = state.team_1_resources();
+        let mut t1_overs = state.first_innings_overs.clone();
+        let mut t2_overs = state.team_2_initial_overs();
+        let mut t2_resources = state.table.resources_remaining(&t2_overs, 0);
+
+        state
+            .interruptions
+            .iter()
+            .map(|int| {
+                let (allocation, par) = match int.innings {
+                    Innings::First => {
+                        let allocation = t1_overs.clone();
+                        t1_overs = &t1_overs - &int.overs_lost;
+                        (allocation, None)
+                    }
+                    Innings::Second => {
+                        let allocation = t2_overs.clone();
+                        t2_overs = &t2_overs - &int.overs_lost;
+                        let resources_used = t2_resources
+                            - state
+                                .table
+                                .resources_remaining(&int.overs_left, int.wickets);
+                        t2_resources -= int.resource_loss(state.table);
+                        let par = par(self.g_50, first_innings_total, t1_resources, resources_used);
+                        (allocation, Some(par as u32))
+                    }
+                };
+                InterruptionSummary {
+                    innings: int.innings.clone(),
+                    wickets: int.wickets,
+                    runs: int.runs,
+                    overs_faced: allocation - &int.overs_left,
+                    overs_lost: int.overs_lost.clone(),
+                    par,
+                }
+            })
+            .collect()
+    }
+
+    /// Describes the recorded interruptions as a series of sentences, one per interruption
+    pub fn narrative(&self, first_innings_total: usize) -> Vec<String> {
+        self.interruption_summaries(first_innings_total)
+            .iter()
+            .map(InterruptionSummary::to_string)
+            .collect()
+    }
+
+    /// Breaks down how the target for the team batting second is calculated: the resources each
+    /// team started with, the resources lost to each interruption, the formula used and the
+    /// resulting par and target
+    ///
+    /// Unlike `revised_target`, the target is always given; if there have been no interruptions
+    /// it is one more than the first innings total
+    pub fn calculation(&self, first_innings_total: usize) -> Calculation {
+        let state = self.state();
+        let (team_1_resources, team_2_resources) = state.resources();
+        let team_2_overs = state.team_2_initial_overs();
+        let formula = match team_2_resources.total_cmp(&team_1_resources) {
+            Ordering::Less => Formula::Scaled,
+            Ordering::Greater => Formula::Increased,
+            Ordering::Equal => Formula::Unchanged,
+        };
+        let target = match self.revised_target(first_innings_total) {
+            0 => first_innings_total as u32 + 1,
+            target => target,
+        };
+
+        Calculation {
+            g_50: self.g_50,
+            first_innings_total,
+            team_1_starting_resources: state
+                .table
+                .resources_remaining(&state.first_innings_overs, 0),
+            team_1_overs: state.first_innings_overs.clone(),
+            team_2_starting_resources: state.table.resources_remaining(&team_2_overs, 0),
+            team_2_overs,
+            interruptions: state
+                .interruptions
+                .iter()
+                .map(|int| ResourceLoss {
+                    innings: int.innings.clone(),
+                    wickets: int.wickets,
+                    overs_left: int.overs_left.clone(),
+                    overs_lost: int.overs_lost.clone(),
+                    at_suspension: state
+                        .table
+                        .resources_remaining(&int.overs_left, int.wickets),
+                    at_resumption: state
+                        .table
+                        .resources_remaining(&(&int.overs_left - &int.overs_lost), int.wickets),
+                })
+                .collect(),
+            team_1_resources,
+            team_2_resources,
+            formula,
+            par: par(
+                self.g_50,
+                first_innings_total,
+                team_1_resources,
+                team_2_resources,
+            ),
+            target,
+        }
+    }
+
+    /// Replays the event log to find the interruptions and allocations that apply to this match
+    fn state(&self) -> State<'_> {
+        let mut state = State {
+            table: self.table(),
+            first_innings_overs: self.length.clone(),
+            team_2_overs: None,
+            interruptions: Vec::new(),
+            stoppage: None,
+            status: MatchStatus::NotStarted,
+        };
+        for event in &self.events {
+            state.apply(event);
+        }
+        state
+    }
+}
+
+impl State<'_> {
+    fn apply(&mut self, event: &MatchEvent) {
+        match event {
+            MatchEvent::InningsStart { innings, .. } => {
+                self.status = MatchStatus::InProgress(innings.clone());
+            }
+            MatchEvent::Stoppage {
+                innings,
+                wickets,
+                runs,
+                overs_left,
+                ..
+            } => {
+                self.stoppage = Some(Interruption {
+                    wickets: *wickets,
+                    runs: *runs,
+                    overs_left: overs_left.clone(),
+                    overs_lost: Overs::new(0),
+                    innings: innings.clone(),
+                });
+                self.status = MatchStatus::Stopped(innings.clone());
+            }
+            MatchEvent::Suspension { .. } => {
+                if let Some(int) = &self.stoppage {
+                    self.status = MatchStatus::Suspended(int.innings.clone());
+                }
+            }
+            MatchEvent::Resumption { overs_lost, .. } => {
+                if let Some(mut int) = self.stoppage.take() {
+                    int.overs_lost = overs_lost.clone();
+                    self.status = MatchStatus::InProgress(int.innings.clone());
+                    self.interruptions.push(int);
+                }
+            }
+            MatchEvent::OversReduction {
+                innings: Innings::First,
+                overs_lost,
+            } => self.first_innings_overs = &self.first_innings_overs - overs_lost,
+            MatchEvent::OversReduction {
+                innings: Innings::Second,
+                overs_lost,
+            } => self.team_2_overs = Some(self.team_2_initial_overs() - overs_lost),
+            MatchEvent::Allocation {
+                innings: Innings::First,
+                overs,
+            } => self.first_innings_overs = overs.clone(),
+            MatchEvent::Allocation {
+                innings: Innings::Second,
+                overs,
+            } => self.team_2_overs = Some(overs.clone()),
+            MatchEvent::InningsEnd {
+                innings: Innings::First,
+                wickets,
+                runs,
+                overs_bowled,
+                ..
+            } => {
+                let overs_left = self.team_1_resources().1 - overs_bowled;
+                if *wickets < 10 && overs_left > Overs::new(0) {
+                    self.interruptions.push(Interruption {
+                        wickets: *wickets,
+                        runs: Some(*runs),
+                        overs_left: overs_left.clone(),
+                        overs_lost: overs_left,
+                        innings: Innings::First,
+                    });
+                }
+                self.status = MatchStatus::InningsBreak;
+            }
+            MatchEvent::InningsEnd {
+                innings: Innings::Second,
+                ..
+            } => self.status = MatchStatus::Completed,
+            MatchEvent::Abandonment { .. } => {
+                if let Some(mut int) = self.stoppage.take() {
+                    int.overs_lost = int.overs_left.clone();
+                    self.interruptions.push(int);
+                }
+                self.status = MatchStatus::Abandoned;
+            }
+        }
+    }
+
+    /// Calculates the resources available to the team batting first after allowing for any
+    /// interruptions in their innings, along with the overs they were ultimately allocated
+    fn team_1_resources(&self) -> (f32, Overs) {
+        self.interruptions
             .iter()
             .filter(|int| int.innings == Innings::First)
             .fold(
-                (self.initial_resources(), self.length.clone()),
+                (
+                    self.table.resources_remaining(&self.first_innings_overs, 0),
+                    self.first_innings_overs.clone(),
+                ),
                 |(resources, overs), int| {
-                    (resources - int.resource_loss(), overs - &int.overs_lost)
+                    (
+                        resources - int.resource_loss(self.table),
+                        overs - &int.overs_lost,
+                    )
                 },
-            );
+            )
+    }
 
-        let t2_resources = DUCKWORTH_LEWIS_TABLE.resources_remaining(&total_overs, 0);
+    /// Calculates the resources available to each team after allowing for every interruption
+    fn resources(&self) -> (f32, f32) {
+        let (t1_resources, _) = self.team_1_resources();
+        let t2_resources = self
+            .table
+            .resources_remaining(&self.team_2_initial_overs(), 0);
         let t2_resources = self
             .interruptions
             .iter()
             .filter(|int| int.innings == Innings::Second)
             .fold(t2_resources, |resources, int| {
-                resources - int.resource_loss()
+                resources - int.resource_loss(self.table)
             });
+        (t1_resources, t2_resources)
+    }
 
-        let revised_target = match t2_resources.total_cmp(&t1_resources) {
-            Ordering::Less => first_innings_total as f32 * (t2_resources / t1_resources) + 1.0,
-            Ordering::Greater => {
-                first_innings_total as f32 + (t2_resources - t1_resources) * self.g_50 / 100.0 + 1.0
-            }
-            Ordering::Equal => first_innings_total as f32,
-        };
-
-        revised_target as u32
+    /// The overs available to the team batting second at the start of their innings
+    fn team_2_initial_overs(&self) -> Overs {
+        match &self.team_2_overs {
+            Some(overs) => overs.clone(),
+            None => self.team_1_resources().1,
+        }
     }
+}
+
+/// The score the team batting second needs to reach to win the match, given the resources
+/// available to both teams
+pub(crate) fn target(
+    g_50: f32,
+    first_innings_total: usize,
+    t1_resources: f32,
+    t2_resources: f32,
+) -> u32 {
+    par(g_50, first_innings_total, t1_resources, t2_resources) as u32 + 1
+}
 
-    /// Calculates the total resources available at the beginning of an innings
-    fn initial_resources(&self) -> f32 {
-        DUCKWORTH_LEWIS_TABLE.resources_remaining(&self.length, 0)
+/// The score the team batting second would need to tie the match, given the resources
+/// available to both teams. Fractional runs are retained so callers can decide how to round
+pub(crate) fn par(
+    g_50: f32,
+    first_innings_total: usize,
+    t1_resources: f32,
+    t2_resources: f32,
+) -> f32 {
+    match t2_resources.total_cmp(&t1_resources) {
+        Ordering::Less => first_innings_total as f32 * (t2_resources / t1_resources),
+        Ordering::Greater => {
+            first_innings_total as f32 + (t2_resources - t1_resources) * g_50 / 100.0
+        }
+        Ordering::Equal => first_innings_total as f32,
     }
 }
 
 impl Interruption {
-    fn resource_loss(&self) -> f32 {
-        let remaining_at_suspension =
-            DUCKWORTH_LEWIS_TABLE.resources_remaining(&self.overs_left, self.wickets);
-        let remaining_at_resumption = DUCKWORTH_LEWIS_TABLE
-            .resources_remaining(&(&self.overs_left - &self.overs_lost), self.wickets);
+    fn resource_loss(&self, table: &DuckworthLewisTable) -> f32 {
+        let remaining_at_suspension = table.resources_remaining(&self.overs_left, self.wickets);
+        let remaining_at_resumption =
+            table.resources_remaining(&(&self.overs_left - &self.overs_lost), self.wickets);
         remaining_at_suspension - remaining_at_resumption
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::game::{CricketMatch, Grade, Innings};
-    use crate::Overs;
+    use std::time::SystemTime;
+
+    use crate::game::{CricketMatch, Formula, Grade, Innings, MatchStatus};
+    use crate::{MatchEvent, Overs, Situation};
 
     /// Based on ICC example found here: https://icc-static-files.s3.amazonaws.com/ICC/document/2017/01/09/ca50a5e9-0241-494a-8773-d0cec059b31f/DuckworthLewis-Methodology.pdf
     #[test]
@@ -234,20 +1134,295 @@ mod test {
 
     /// Based on ICC example found here: https://icc-static-files.s3.amazonaws.com/ICC/document/2017/01/09/ca50a5e9-0241-494a-8773-d0cec059b31f/DuckworthLewis-Methodology.pdf
     ///
-    /// Note that this lib doesn't provide an API to calculate the par score. Therefore the target
-    /// in this case (160) is different from the par score provided in the ICC example (159) above
+    /// Note that the target in this case (160) is different from the par score provided in the
+    /// ICC example (159) above
     #[test]
     fn icc_example_four() {
+        let game = icc_example_four_match();
+        let revised_total = game.revised_target(250);
+        assert_eq!(revised_total, 160);
+        assert_eq!(game.revised_par(250), 159);
+    }
+
+    #[test]
+    fn calculation_breaks_down_icc_example_one() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(3, 95, Overs::new(30), Overs::new(10), Innings::First);
+        let calculation = game.calculation(180);
+
+        assert_eq!(calculation.team_1_starting_resources, 100.0);
+        assert_eq!(calculation.team_2_overs, Overs::new(40));
+        assert_eq!(calculation.interruptions.len(), 1);
+        let lost = calculation.interruptions[0].lost();
+        assert!((calculation.team_1_resources - (100.0 - lost)).abs() < 1e-4);
+        assert_eq!(calculation.formula, Formula::Increased);
+        assert_eq!(calculation.target, game.revised_target(180));
+    }
+
+    #[test]
+    fn calculation_without_interruptions_needs_one_more_run() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let calculation = game.calculation(250);
+
+        assert_eq!(calculation.formula, Formula::Unchanged);
+        assert_eq!(calculation.target, 251);
+    }
+
+    #[test]
+    fn interruption_with_runs_reports_par_at_the_stoppage() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        let summaries = game.interruption_summaries(250);
+
+        assert_eq!(summaries[0].runs, Some(55));
+        assert_eq!(summaries[0].ahead(), Some(true));
+        assert_eq!(game.revised_target(250), 218);
+    }
+
+    #[test]
+    fn interruption_without_runs_has_unknown_position_against_par() {
         let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
         game.interruption(1, Overs::new(38), Overs::new(10), Innings::Second);
-        game.interruption(3, Overs::new(18), Overs::new(2), Innings::Second);
-        game.interruption(
+        let summaries = game.interruption_summaries(250);
+
+        assert_eq!(summaries[0].runs, None);
+        assert_eq!(summaries[0].ahead(), None);
+        assert!(summaries[0].par.is_some());
+        assert_eq!(
+            game.narrative(250)[0],
+            format!(
+                "Second innings interrupted after 12 overs with 1 down, 10 overs lost; par {}",
+                summaries[0].par.unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn interruption_without_runs_gives_the_same_target() {
+        let mut without_runs = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        without_runs.interruption(3, Overs::new(30), Overs::new(10), Innings::First);
+        let mut with_runs = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        with_runs.interruption_with_runs(3, 95, Overs::new(30), Overs::new(10), Innings::First);
+
+        assert_eq!(without_runs.revised_target(180), 185);
+        assert_eq!(
+            without_runs.revised_target(180),
+            with_runs.revised_target(180)
+        );
+    }
+
+    #[test]
+    fn calculation_explains_each_step() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        let explanation = game.calculation(250).to_string();
+        let lines: Vec<_> = explanation.lines().collect();
+
+        assert_eq!(
+            lines[1],
+            "Team 1 starts with 50 overs: resources available 100.0%"
+        );
+        assert!(lines[4].starts_with("  Interrupted with 38 overs left and 1 wicket down"));
+        assert!(lines[6].starts_with("R2 < R1, so par = S × R2 / R1 = 250 × "));
+        assert_eq!(lines[7], "Target = 218");
+    }
+
+    #[test]
+    fn grades_and_innings_parse_from_cli_names() {
+        assert!(matches!("first-class".parse(), Ok(Grade::FirstClass)));
+        assert!(matches!(
+            "ICC-Full-Member".parse(),
+            Ok(Grade::ICCFullMember)
+        ));
+        assert!("county".parse::<Grade>().is_err());
+        assert_eq!("second".parse::<Innings>().unwrap(), Innings::Second);
+    }
+
+    #[test]
+    fn icc_example_four_target_history() {
+        let game = icc_example_four_match();
+        let history = game.target_history(250);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].overs, Overs::new(40));
+        assert_eq!(history[0].target, 218);
+        assert_eq!(history[1].overs, Overs::new(38));
+        assert_eq!(history[2].interruption, 2);
+        assert_eq!(history[2].par, 159);
+        assert_eq!(history[2].target, 160);
+    }
+
+    /// The final interruption in ICC example four is the abandonment of the match, so the
+    /// par at that interruption should match the par given in the example
+    #[test]
+    fn icc_example_four_par_at_abandonment() {
+        let game = icc_example_four_match();
+        let summaries = game.interruption_summaries(250);
+
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[2].par, Some(159));
+        assert_eq!(summaries[2].ahead(), Some(false));
+        assert_eq!(summaries[2].overs_faced, "30.2".parse().unwrap());
+    }
+
+    #[test]
+    fn first_innings_interruptions_have_no_par() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(3, 95, Overs::new(30), Overs::new(10), Innings::First);
+        let summaries = game.interruption_summaries(180);
+
+        assert_eq!(summaries[0].par, None);
+        assert_eq!(summaries[0].ahead(), None);
+        assert_eq!(
+            game.narrative(180),
+            vec!["First innings interrupted after 20 overs at 95/3, 10 overs lost"]
+        );
+    }
+
+    #[test]
+    fn terminated_first_innings_gives_team_2_same_overs() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.terminate_first_innings(5, 200, Overs::new(40));
+
+        assert_eq!(game.allocation(&Innings::First), Overs::new(40));
+        assert_eq!(game.allocation(&Innings::Second), Overs::new(40));
+        assert_eq!(game.revised_target(200), 238);
+    }
+
+    #[test]
+    fn delay_between_innings_reduces_team_2_allocation() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.terminate_first_innings(5, 200, Overs::new(40));
+        game.delay_between_innings(Overs::new(5));
+
+        assert_eq!(game.allocation(&Innings::Second), Overs::new(35));
+        assert_eq!(game.revised_target(200), 222);
+    }
+
+    #[test]
+    fn team_2_allocation_can_be_set_without_interruptions() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.set_team_2_overs(Overs::new(40));
+
+        assert_eq!(game.allocation(&Innings::Second), Overs::new(40));
+        assert_eq!(game.revised_target(250), 224);
+    }
+
+    #[test]
+    fn abandonment_during_stoppage_loses_remaining_overs() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        game.interruption_with_runs(3, 102, Overs::new(18), Overs::new(2), Innings::Second);
+        game.record(MatchEvent::Stoppage {
+            innings: Innings::Second,
+            wickets: 6,
+            runs: Some(151),
+            overs_left: 7.4.try_into().unwrap(),
+            at: Some(SystemTime::now()),
+        });
+        assert_eq!(game.status(), MatchStatus::Stopped(Innings::Second));
+
+        game.abandon();
+        assert_eq!(game.status(), MatchStatus::Abandoned);
+        assert_eq!(game.revised_target(250), 160);
+    }
+
+    #[test]
+    fn replaying_part_of_the_log_recalculates_earlier_targets() {
+        let game = icc_example_four_match();
+
+        assert_eq!(game.events().len(), 6);
+        assert_eq!(game.as_at(2).revised_target(250), 218);
+        assert_eq!(game.as_at(0).revised_target(250), 0);
+        assert_eq!(
+            game.as_at(5).status(),
+            MatchStatus::Stopped(Innings::Second)
+        );
+    }
+
+    #[test]
+    fn reserve_day_resumption_matches_an_interruption() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.suspend(&Situation {
+            innings: Innings::Second,
+            wickets: 1,
+            runs: 55,
+            overs_left: Overs::new(38),
+        });
+        assert_eq!(game.status(), MatchStatus::Suspended(Innings::Second));
+
+        game.resume(Overs::new(28));
+        let mut same_day = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        same_day.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+
+        assert_eq!(game.status(), MatchStatus::InProgress(Innings::Second));
+        assert_eq!(game.revised_target(250), same_day.revised_target(250));
+        assert_eq!(game.target_history(250), same_day.target_history(250));
+    }
+
+    #[test]
+    fn first_innings_suspension_carries_over_to_team_2() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.suspend(&Situation {
+            innings: Innings::First,
+            wickets: 3,
+            runs: 211,
+            overs_left: "3.1".parse().unwrap(),
+        });
+        game.resume(Overs::new(0));
+
+        assert_eq!(game.allocation(&Innings::First), "46.5".parse().unwrap());
+        assert_eq!(game.allocation(&Innings::Second), "46.5".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn resume_requires_a_suspension() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        game.resume(Overs::new(20));
+    }
+
+    #[test]
+    #[should_panic]
+    fn resumption_requires_a_stoppage() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.record(MatchEvent::Resumption {
+            overs_lost: Overs::new(5),
+            at: None,
+        });
+    }
+
+    #[cfg(feature = "ser")]
+    #[test]
+    fn reads_matches_stored_as_interruptions() {
+        let stored = r#"{
+            "length": {"overs": 50, "balls": 0},
+            "g_50": 245.0,
+            "interruptions": [{
+                "wickets": 1,
+                "overs_left": {"overs": 38, "balls": 0},
+                "overs_lost": {"overs": 10, "balls": 0},
+                "innings": "Second"
+            }]
+        }"#;
+        let game: CricketMatch = serde_json::from_str(stored).unwrap();
+
+        assert_eq!(game.events().len(), 2);
+        assert_eq!(game.revised_target(250), 218);
+    }
+
+    fn icc_example_four_match() -> CricketMatch {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        game.interruption_with_runs(3, 102, Overs::new(18), Overs::new(2), Innings::Second);
+        game.interruption_with_runs(
             6,
+            151,
             7.4.try_into().unwrap(),
             7.4.try_into().unwrap(),
             Innings::Second,
         );
-        let revised_total = game.revised_target(250);
-        assert_eq!(revised_total, 160)
+        game
     }
 }
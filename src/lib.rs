@@ -9,27 +9,105 @@
 //! # Features
 //! cli: Produces a binary that provides a command line interface for using the calculator
 //!
-//! ser: Allows the various objects in this crate to be de/serializable using Serde. Included if cli feature used
+//! ser: Allows the various objects in this crate to be de/serializable using Serde. Also enables reading historical
+//! matches in the Cricsheet JSON format, and estimating G50 or fitting a resource table from them. Included if cli
+//! feature used
+//!
+//! csv: Allows a season of fixtures to be recomputed from a CSV file. Included if cli feature used
+//!
+//! parallel: Allows `BatchEvaluator` to evaluate scenarios across threads using rayon
+//!
+//! sim: Provides a Monte Carlo simulator that plays out matches ball by ball, consistent with the resource table
 
 use std::num::ParseIntError;
 
 use thiserror::Error;
 
-pub use game::{CricketMatch, Grade, Innings};
+pub use bowling::{BowlerLimit, BowlerLimits, BowlingRules};
+#[cfg(feature = "ser")]
+pub use calibration::{estimate_g_50, G50Estimate};
+pub use competition::{MatchOutcome, PointsRules, Standing, Standings};
+#[cfg(feature = "ser")]
+pub use cricsheet::{
+    CricsheetDelivery, CricsheetExtras, CricsheetInfo, CricsheetInnings, CricsheetMatch,
+    CricsheetOutcome, CricsheetOver, CricsheetRuns, CricsheetTarget, CricsheetWicket,
+    ImportedMatch, OverGap, Progress,
+};
+pub use divergence::DivergenceWarning;
+pub use evaluator::{BatchEvaluator, Scenario, ScenarioInterruption};
+pub use event::MatchEvent;
+#[cfg(feature = "ser")]
+pub use fitting::{fit_resource_table, ResourceFit};
+#[cfg(feature = "csv")]
+pub use fixtures::{recompute_fixtures, Fixture, FixtureResult, FixtureSummary};
+pub use game::{
+    Calculation, CricketMatch, Formula, Grade, Innings, InterruptionSummary, MatchStatus,
+    ResourceLoss, TargetRevision,
+};
+pub use nrr::{NetRunRate, NetRunRateCredit, Score};
 pub use overs::Overs;
+pub use powerplay::{Powerplay, PowerplayAllocation, PowerplayRules};
+pub use projection::{Projection, ProjectionMethod};
+pub use rain_rules::{RainRule, RainRuleCurves, RainRuleTarget, RunCurve};
+pub use schedule::{ClockTime, Schedule, ScheduleRevision};
+pub use sensitivity::{SensitivityGrid, Situation};
+#[cfg(feature = "sim")]
+pub use simulation::{SimulatedMatch, SimulatedResult, SimulationReport, Simulator};
+pub use table::DuckworthLewisTable;
 
+mod bowling;
+#[cfg(feature = "ser")]
+mod calibration;
+mod competition;
+#[cfg(feature = "ser")]
+mod cricsheet;
+mod divergence;
+mod evaluator;
+mod event;
+#[cfg(feature = "ser")]
+mod fitting;
+#[cfg(feature = "csv")]
+mod fixtures;
 mod game;
+mod nrr;
 mod overs;
+mod powerplay;
+mod probability;
+mod projection;
+mod rain_rules;
+mod schedule;
+mod sensitivity;
+#[cfg(feature = "sim")]
+mod simulation;
 mod table;
 
 #[derive(Error, Debug)]
 pub enum DuckworthLewisError {
+    #[cfg(feature = "ser")]
+    #[error("could not read cricsheet match: {0}")]
+    InvalidCricsheet(String),
     #[error("overs must be in the format <overs>.<balls> got {0}")]
     InvalidOverFormat(String),
     #[error("balls must be less than 6, got {0}")]
     TooManyBalls(u16),
+    #[cfg(feature = "csv")]
+    #[error("could not read fixtures: {0}")]
+    InvalidCsv(String),
+    #[cfg(feature = "csv")]
+    #[error("invalid fixture: {0}")]
+    InvalidFixture(String),
+    #[error("times must be in the format HH:MM, got {0}")]
+    InvalidTime(String),
     #[error("{0}")]
     OversNotNumeric(String),
+    #[error("unknown grade {0}")]
+    UnknownGrade(String),
+    #[error("innings must be first or second, got {0}")]
+    UnknownInnings(String),
+    #[error("expected runs for each of team 1's {0} overs, got {1}")]
+    WrongOverScoreCount(usize, usize),
+    #[error("run curves must be percentages starting at 0 and never decreasing, got {0}")]
+    InvalidRunCurve(String),
 }
 
 impl From<ParseIntError> for DuckworthLewisError {
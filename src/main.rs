@@ -1,15 +1,28 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 
-use duckworth_lewis::{CricketMatch, Grade, Innings, Overs};
+use duckworth_lewis::{
+    estimate_g_50, recompute_fixtures, BowlingRules, ClockTime, CricketMatch, CricsheetMatch,
+    Grade, Innings, MatchEvent, MatchStatus, Overs, PointsRules, PowerplayAllocation,
+    PowerplayRules, ProjectionMethod, RainRule, RainRuleCurves, RunCurve, Schedule, Score,
+    Situation, Standings,
+};
 
-type Store = HashMap<usize, MatchWrapper>;
+/// Everything held in the match store. Older stores only held matches keyed by id, so those
+/// are read as a store without any competitions
+#[derive(Default, Serialize, Deserialize)]
+struct Store {
+    matches: HashMap<usize, MatchWrapper>,
+    #[serde(default)]
+    competitions: HashMap<usize, Competition>,
+}
 
 #[derive(Debug, Parser)]
 #[command(name = "dlc")]
@@ -24,6 +37,83 @@ struct Cli {
     /// Specify the id of the match you are updating; if not provided defaults to latest match created
     #[clap(short, long)]
     id: Option<usize>,
+    /// How results of the new, int, target, list and delete commands are printed; json and csv are intended for scripts
+    #[clap(long, value_enum, global = true, default_value = "text")]
+    output: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+/// A match created by the new command
+#[derive(Serialize)]
+struct CreatedMatch<'a> {
+    match_id: usize,
+    team_1: &'a str,
+    team_2: &'a str,
+}
+
+/// Shown when a command needs the first innings total but it wasn't given and hasn't been recorded
+const UNKNOWN_FIRST_INNINGS_TOTAL: &str = "first innings total not known; provide it or record the completed innings with the score command";
+
+/// Where a match is up to after an interruption has been recorded
+#[derive(Serialize)]
+struct MatchState {
+    match_id: usize,
+    status: String,
+    team_1_overs: String,
+    team_2_overs: String,
+    team_1_resources: f32,
+    team_2_resources: f32,
+    /// The revised powerplays for the interrupted innings
+    powerplays: String,
+    /// The revised limits on overs per bowler for the interrupted innings
+    bowler_limits: String,
+}
+
+/// The breakdown of a target, without the individual interruptions so it fits in a CSV row
+#[derive(Serialize)]
+struct TargetBreakdown {
+    match_id: usize,
+    first_innings_total: usize,
+    team_1_overs: String,
+    team_2_overs: String,
+    team_1_resources: f32,
+    team_2_resources: f32,
+    formula: String,
+    par: f32,
+    target: u32,
+    divergence_warning: Option<String>,
+}
+
+/// The text output of the target command, so it always shows the same target as JSON and CSV
+impl Display for TargetBreakdown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Adjusted target for team 2 is {}", self.target)
+    }
+}
+
+/// A row of the list command
+#[derive(Serialize)]
+struct ListedMatch<'a> {
+    match_id: usize,
+    team_1: &'a str,
+    team_2: &'a str,
+    /// Seconds since the Unix epoch
+    creation: u64,
+    length: String,
+    g_50: f32,
+    status: String,
+}
+
+/// A match removed by the delete command
+#[derive(Serialize)]
+struct DeletedMatch {
+    match_id: usize,
 }
 
 #[derive(Subcommand, Debug)]
@@ -43,6 +133,15 @@ enum Commands {
         #[clap(long = "team_2")]
         #[arg(default_value = "Team 2")]
         team_2: String,
+        /// Use a custom G50 value instead of the one for the grade (e.g. one suggested by calibrate)
+        #[clap(long = "g_50")]
+        g_50: Option<u16>,
+        /// Warn when the first innings total is more than this multiple of G50, as the standard edition becomes unfair [default: 1.2, or the competition's threshold]
+        #[clap(long = "divergence_threshold")]
+        divergence_threshold: Option<f32>,
+        /// Id of the competition this match is part of
+        #[clap(long)]
+        competition: Option<usize>,
     },
     /// Add an interruption to an existing match
     Int {
@@ -54,11 +153,203 @@ enum Commands {
         overs_lost: Overs,
         /// Which innings the interruption occurred during
         innings: Innings,
+        /// Total runs scored in the innings so far; if not given, history and show can't say whether team 2 were ahead of par
+        #[clap(long)]
+        runs: Option<u16>,
+        /// Playing conditions used to show the revised powerplays; defaults to t20i for matches of 20 overs or fewer and odi otherwise
+        #[clap(long, value_enum)]
+        powerplays: Option<PowerplayRules>,
+        /// How the one fifth limit on overs per bowler is applied when showing the revised limits
+        #[clap(long, value_enum, default_value = "one-fifth")]
+        bowling: BowlingRules,
+    },
+    /// Record that the first innings was ended early and will not be resumed
+    Terminate {
+        /// Total wickets lost in the first innings
+        wickets: u16,
+        /// Total runs scored in the first innings
+        runs: u16,
+        /// Overs bowled in the first innings when it was terminated
+        overs_bowled: Overs,
+    },
+    /// Record a delay between innings that reduces the overs available to team 2
+    Break {
+        /// Overs lost from team 2's allocation because of the delay
+        overs_lost: Overs,
+    },
+    /// Set the overs available to team 2 at the start of their innings directly
+    Allocate {
+        /// Overs team 2 will have available when their innings starts
+        overs: Overs,
     },
     /// Calculate the current second innings target for an existing match; this can be safely recalculated if additional interruptions occur
     Target {
-        /// Total runs scored in the first innings (not the par score)
-        first_innings_total: usize,
+        /// Total runs scored in the first innings (not the par score); if not provided the score recorded with the score command is used
+        first_innings_total: Option<usize>,
+    },
+    /// Show each step of the target calculation: the resources available to each team, the resources lost to each interruption, the formula used and the par score
+    Explain {
+        /// Total runs scored in the first innings (not the par score); if not provided the score recorded with the score command is used
+        first_innings_total: Option<usize>,
+    },
+    /// Show how team 2's target has changed after each interruption to their innings
+    History {
+        /// Total runs scored in the first innings (not the par score); if not provided the score recorded with the score command is used
+        first_innings_total: Option<usize>,
+    },
+    /// Compare the target given by Duckworth Lewis with those from older rain rules (ARR, MPO, DMPO, Parabola and VJD)
+    Compare {
+        /// Total runs scored in the first innings (not the par score); if not provided the score recorded with the score command is used
+        first_innings_total: Option<usize>,
+        /// Runs team 1 scored in each over, separated by commas; needed for the most productive overs methods
+        #[clap(long = "over_scores", value_delimiter = ',')]
+        over_scores: Vec<u16>,
+        /// File of percentages from the published Parabola table, one for each over from 0; needed for the Parabola method
+        #[clap(long)]
+        parabola: Option<PathBuf>,
+        /// File of percentages from the published VJD normal curve, one for each over from 0; needed for the VJD method
+        #[clap(long = "vjd_normal")]
+        vjd_normal: Option<PathBuf>,
+        /// File of percentages from the published VJD target curve, one for each over from 0; needed for the VJD method
+        #[clap(long = "vjd_target")]
+        vjd_target: Option<PathBuf>,
+    },
+    /// Show how the target would change if play was interrupted now, for a range of further wickets and overs lost
+    Whatif {
+        /// Total wickets lost in the innings so far
+        wickets: u16,
+        /// Total runs scored in the innings so far
+        runs: u16,
+        /// Overs remaining in the innings
+        overs_left: Overs,
+        /// Which innings is in progress
+        innings: Innings,
+        /// Total runs scored in the first innings (not the par score); if not provided the score recorded with the score command is used
+        #[clap(long = "first_innings_total")]
+        first_innings_total: Option<usize>,
+        /// How many further wickets to consider falling before play stops
+        #[clap(long = "extra_wickets")]
+        #[arg(default_value = "2")]
+        extra_wickets: u16,
+        /// The overs lost to consider, separated by commas
+        #[clap(long = "overs_lost", value_delimiter = ',')]
+        #[arg(default_value = "5,10,15,20")]
+        overs_lost: Vec<Overs>,
+    },
+    /// Project the final total for an innings in progress
+    Project {
+        /// Total wickets lost in the innings so far
+        wickets: u16,
+        /// Total runs scored in the innings so far
+        runs: u16,
+        /// Overs remaining in the innings
+        overs_left: Overs,
+        /// Which innings is in progress
+        #[arg(value_enum, default_value = "first")]
+        innings: Innings,
+    },
+    /// Show the scheduled close of play and cut-off times, and record the overs lost to a delay if one is given; the schedule is kept with the match, so later delays only have the extra time that is left
+    Schedule {
+        /// Scheduled start of play, e.g. 10:30; only needed the first time, otherwise the start already recorded is used
+        start: Option<ClockTime>,
+        /// Overs expected to be bowled per hour [default: 14.1 for matches of 20 overs or fewer, 14.3 otherwise]
+        #[clap(long = "over_rate")]
+        over_rate: Option<f32>,
+        /// Length of the interval between innings in minutes [default: 20 for matches of 20 overs or fewer, 45 otherwise]
+        #[clap(long)]
+        interval: Option<u16>,
+        /// Extra time available in minutes, including any already used by earlier delays [default: 30 for matches of 20 overs or fewer, 60 otherwise]
+        #[clap(long = "extra_time")]
+        extra_time: Option<u16>,
+        /// Fewest overs each side must face to constitute a match [default: 5 for matches of 20 overs or fewer, 20 otherwise]
+        #[clap(long = "minimum_overs")]
+        minimum_overs: Option<u16>,
+        /// Minutes of playing time lost to a delay; the overs lost are recorded as an interruption
+        #[clap(long)]
+        delay: Option<u16>,
+        /// Which innings the delay occurred during (a delay before the match starts is in the first innings)
+        #[clap(long, value_enum, default_value = "first")]
+        innings: Innings,
+        /// Total wickets lost in the innings when play stopped
+        #[clap(long, default_value = "0")]
+        wickets: u16,
+        /// Total runs scored in the innings when play stopped
+        #[clap(long, default_value = "0")]
+        runs: u16,
+        /// Overs remaining in the innings when play stopped [default: the innings' current allocation]
+        #[clap(long = "overs_left")]
+        overs_left: Option<Overs>,
+    },
+    /// Suggest a G50 value from the first innings of historical matches in Cricsheet JSON format
+    Calibrate {
+        /// Cricsheet JSON files to read
+        files: Vec<PathBuf>,
+    },
+    /// Recompute targets for a CSV of fixtures (columns length, grade, g_50, first_innings_total, interruptions), writing a CSV of results
+    Batch {
+        /// CSV file of fixtures; interruptions are written innings:wickets:runs:overs_left:overs_lost and separated by semicolons
+        input: PathBuf,
+        /// Where to write the results; if not provided they are printed
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Suspend play until another day (e.g. a reserve day), keeping the situation when play stopped
+    Suspend {
+        /// Total wickets lost in the innings so far
+        wickets: u16,
+        /// Total runs scored in the innings so far
+        runs: u16,
+        /// Overs remaining in the innings when play was suspended
+        overs_left: Overs,
+        /// Which innings was in progress (use second with no wickets or runs for a suspension between innings)
+        innings: Innings,
+    },
+    /// Resume a suspended match from where it was suspended
+    Resume {
+        /// Overs remaining in the suspended innings now that play is resuming; any other overs are lost
+        overs_left: Overs,
+    },
+    /// Record the score of an innings, either at the end of the innings or as it progresses, so it doesn't need to be given to other commands
+    Score {
+        /// Which innings the score is for
+        innings: Innings,
+        /// Total runs scored in the innings
+        runs: u16,
+        /// Total wickets lost in the innings
+        wickets: u16,
+        /// Overs faced in the innings
+        overs: Overs,
+        /// The innings has ended; a first innings score is only used as the total for targets once it has, which is assumed if all 10 wickets have fallen or all the overs have been faced
+        #[clap(long)]
+        complete: bool,
+    },
+    /// Record the final scores of a completed match, for use in competition tables; if the scores aren't provided those recorded with the score command are used
+    Result {
+        /// Total runs scored in the first innings
+        first_runs: Option<u16>,
+        /// Total wickets lost in the first innings
+        first_wickets: Option<u16>,
+        /// Overs faced in the first innings
+        first_overs: Option<Overs>,
+        /// Total runs scored in the second innings
+        second_runs: Option<u16>,
+        /// Total wickets lost in the second innings
+        second_wickets: Option<u16>,
+        /// Overs faced in the second innings
+        second_overs: Option<Overs>,
+    },
+    /// Record that a match finished without a result
+    NoResult,
+    /// Manage competitions made up of matches in the store
+    #[command(subcommand)]
+    Comp(CompCommands),
+    /// Import a match from another format into the store
+    #[command(subcommand)]
+    Import(ImportFormat),
+    /// Show everything recorded for a match: its length, G50, teams, when it was created, each interruption and the current target if the first innings total is known
+    Show {
+        /// Match id to show; if not provided defaults to the id option, then to the latest match created
+        match_id: Option<usize>,
     },
     /// List all matches that are currently held in the match store
     List,
@@ -69,6 +360,59 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum CompCommands {
+    /// Create a new competition
+    New {
+        /// Name of the competition
+        name: String,
+        /// Teams taking part, separated by commas
+        #[clap(long, value_delimiter = ',')]
+        teams: Vec<String>,
+        /// Points for a win
+        #[clap(long, default_value = "2")]
+        win: u16,
+        /// Points for a tie
+        #[clap(long, default_value = "1")]
+        tie: u16,
+        /// Points for a match without a result
+        #[clap(long = "no_result", default_value = "1")]
+        no_result: u16,
+        /// Points for a loss
+        #[clap(long, default_value = "0")]
+        loss: u16,
+        /// Bonus points for a winning team whose run rate was well ahead of their opponent's
+        #[clap(long, default_value = "0")]
+        bonus: u16,
+        /// How many times their opponent's run rate a winning team needs to get bonus points
+        #[clap(long = "bonus_ratio", default_value = "1.25")]
+        bonus_ratio: f32,
+        /// Warn when a first innings total in this competition is more than this multiple of G50
+        #[clap(long = "divergence_threshold")]
+        divergence_threshold: Option<f32>,
+    },
+    /// Show the points table for a competition, including net run rate adjusted for rain affected matches
+    Table {
+        /// Competition id; if not provided defaults to latest competition created
+        competition_id: Option<usize>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ImportFormat {
+    /// Import a match in Cricsheet JSON format, comparing the target calculated here with the one recorded
+    Cricsheet {
+        /// Cricsheet JSON file to read
+        file: PathBuf,
+        /// Highest grade the teams playing in this match are eligible to play
+        #[arg(value_enum, default_value = "icc-full-member")]
+        grade: Grade,
+        /// Use a custom G50 value instead of the one for the grade
+        #[clap(long = "g_50")]
+        g_50: Option<u16>,
+    },
+}
+
 /// A simple wrapper that allows saving matches with ids
 #[derive(Serialize, Deserialize)]
 struct MatchWrapper {
@@ -77,34 +421,466 @@ struct MatchWrapper {
     game: CricketMatch,
     team_1: String,
     team_2: String,
+    /// The grade the match was created with; None if a custom G50 was used
+    #[serde(default)]
+    grade: Option<Grade>,
+    #[serde(default)]
+    competition: Option<usize>,
+    #[serde(default)]
+    result: Option<MatchResult>,
+    /// The latest score recorded for each innings
+    #[serde(default)]
+    first_innings: Option<Score>,
+    #[serde(default)]
+    second_innings: Option<Score>,
+    /// Whether the first innings score was recorded as the final total
+    #[serde(default)]
+    first_innings_complete: bool,
+    /// The day's timings and the delays recorded against them with the schedule command
+    #[serde(default)]
+    schedule: Option<Schedule>,
+}
+
+/// How a completed match finished
+#[derive(Serialize, Deserialize)]
+enum MatchResult {
+    Completed { first: Score, second: Score },
+    NoResult,
+}
+
+/// A group of matches that share a points table
+#[derive(Serialize, Deserialize)]
+struct Competition {
+    competition_id: usize,
+    creation: Duration,
+    name: String,
+    teams: Vec<String>,
+    rules: PointsRules,
+    divergence_threshold: Option<f32>,
 }
 
 impl MatchWrapper {
-    fn new(
-        match_id: usize,
-        length: Overs,
-        grade: Grade,
-        team_1: String,
-        team_2: String,
-    ) -> MatchWrapper {
+    fn new(match_id: usize, game: CricketMatch, team_1: String, team_2: String) -> MatchWrapper {
         MatchWrapper {
             match_id,
-            game: CricketMatch::new(length, grade),
-            creation: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("time went backwards"),
+            game,
+            creation: now(),
             team_1,
             team_2,
+            grade: None,
+            competition: None,
+            result: None,
+            first_innings: None,
+            first_innings_complete: false,
+            second_innings: None,
+            schedule: None,
         }
     }
 
-    fn add_int(&mut self, wickets: u16, overs_left: Overs, overs_lost: Overs, innings: Innings) {
+    fn add_int(
+        &mut self,
+        wickets: u16,
+        runs: Option<u16>,
+        overs_left: Overs,
+        overs_lost: Overs,
+        innings: Innings,
+    ) {
+        match runs {
+            Some(runs) => self
+                .game
+                .interruption_with_runs(wickets, runs, overs_left, overs_lost, innings),
+            None => self
+                .game
+                .interruption(wickets, overs_left, overs_lost, innings),
+        }
+    }
+
+    fn print_revised_limits(
+        &self,
+        innings: &Innings,
+        powerplays: Option<PowerplayRules>,
+        bowling: BowlingRules,
+    ) {
+        println!("{}", self.revised_powerplays(innings, powerplays));
+        println!(
+            "Bowling limits for {}",
+            self.game.bowler_limits(innings, bowling)
+        );
+    }
+
+    /// The powerplays for an innings, using the usual rules for the match length if none are given
+    fn revised_powerplays(
+        &self,
+        innings: &Innings,
+        powerplays: Option<PowerplayRules>,
+    ) -> PowerplayAllocation {
+        let rules = powerplays.unwrap_or_else(|| PowerplayRules::for_length(self.game.length()));
+        self.game.powerplays(innings, rules)
+    }
+
+    fn print_schedule(&self, schedule: &Schedule) {
+        println!("Scheduled close of play: {}", schedule.scheduled_close());
+        println!(
+            "Latest start for a {} over match: {}",
+            schedule.minimum_overs,
+            schedule.latest_start()
+        );
+        println!(
+            "Latest start of the second innings for {} overs: {}",
+            schedule.minimum_overs,
+            schedule.second_innings_cut_off()
+        );
+    }
+
+    fn record_delay(&mut self, schedule: &mut Schedule, minutes_lost: u16, situation: &Situation) {
+        let revision = schedule.record_delay(&mut self.game, minutes_lost, situation);
+        println!(
+            "Delay of {} minutes: {} minutes of extra time used ({} remaining), {} overs lost",
+            minutes_lost,
+            revision.extra_time_used,
+            revision.extra_time_remaining,
+            revision.overs_lost
+        );
+        println!(
+            "{} overs available to {}, {} overs available to {}",
+            self.game.allocation(&Innings::First),
+            self.team_1,
+            self.game.allocation(&Innings::Second),
+            self.team_2
+        );
+        println!("Revised close of play: {}", revision.close_of_play);
+    }
+
+    fn terminate(&mut self, wickets: u16, runs: u16, overs_bowled: Overs) {
         self.game
-            .interruption(wickets, overs_left, overs_lost, innings);
+            .terminate_first_innings(wickets, runs, overs_bowled.clone());
+        self.first_innings = Some(Score {
+            runs,
+            wickets,
+            overs: overs_bowled,
+        });
+    }
+
+    fn add_break(&mut self, overs_lost: Overs) {
+        self.game.delay_between_innings(overs_lost);
+    }
+
+    fn allocate(&mut self, overs: Overs) {
+        self.game.set_team_2_overs(overs);
+    }
+
+    /// Checks a score is possible: no more than 10 wickets, and no more overs than the innings
+    /// has been allocated
+    fn check_score(&self, score: &Score, innings: &Innings) -> Result<(), String> {
+        let allocation = self.game.allocation(innings);
+        if score.wickets > 10 {
+            Err(format!(
+                "at most 10 wickets can fall, got {}",
+                score.wickets
+            ))
+        } else if score.overs > allocation {
+            Err(format!(
+                "{} overs is more than the {} overs allocated to the innings",
+                score.overs, allocation
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The score of an innings given to the result command: runs, wickets and overs if all of
+    /// them are given, otherwise the score recorded with the score command
+    fn final_score(
+        &self,
+        runs: Option<u16>,
+        wickets: Option<u16>,
+        overs: Option<Overs>,
+        innings: &Innings,
+    ) -> Result<Score, String> {
+        let (recorded, name) = match innings {
+            Innings::First => (&self.first_innings, "first"),
+            Innings::Second => (&self.second_innings, "second"),
+        };
+        match (runs, wickets, overs) {
+            (Some(runs), Some(wickets), Some(overs)) => Ok(Score {
+                runs,
+                wickets,
+                overs,
+            }),
+            (None, None, None) => recorded.clone().ok_or_else(|| {
+                format!(
+                    "{} innings score not known; give it or record it with the score command",
+                    name
+                )
+            }),
+            _ => Err(format!(
+                "give the runs, wickets and overs for the {} innings, or none of them",
+                name
+            )),
+        }
+    }
+
+    /// Checks a result can be recorded: both scores must be possible and both innings must have
+    /// faced at least a ball, and if the match is part of a competition that lists its teams,
+    /// both teams must be among them
+    fn check_result(
+        &self,
+        first: &Score,
+        second: &Score,
+        competition_teams: Option<&[String]>,
+    ) -> Result<(), String> {
+        self.check_score(first, &Innings::First)?;
+        self.check_score(second, &Innings::Second)?;
+        if first.overs.total_balls() == 0 || second.overs.total_balls() == 0 {
+            return Err("both innings must have faced at least one ball".to_string());
+        }
+        match competition_teams {
+            Some(teams) if !teams.is_empty() => [&self.team_1, &self.team_2]
+                .into_iter()
+                .find(|team| !teams.contains(team))
+                .map_or(Ok(()), |team| {
+                    Err(format!("{} is not one of the competition's teams", team))
+                }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The match after an interruption, with the revised limits for the interrupted innings
+    fn state(
+        &self,
+        innings: &Innings,
+        powerplays: Option<PowerplayRules>,
+        bowling: BowlingRules,
+    ) -> MatchState {
+        MatchState {
+            match_id: self.match_id,
+            status: self.game.status().to_string(),
+            team_1_overs: self.game.allocation(&Innings::First).to_string(),
+            team_2_overs: self.game.allocation(&Innings::Second).to_string(),
+            team_1_resources: self.game.resources(&Innings::First),
+            team_2_resources: self.game.resources(&Innings::Second),
+            powerplays: self.revised_powerplays(innings, powerplays).to_string(),
+            bowler_limits: self.game.bowler_limits(innings, bowling).to_string(),
+        }
+    }
+
+    fn target_breakdown(&self, first_innings_total: usize) -> TargetBreakdown {
+        let calculation = self.game.calculation(first_innings_total);
+        TargetBreakdown {
+            match_id: self.match_id,
+            first_innings_total,
+            team_1_overs: self.game.allocation(&Innings::First).to_string(),
+            team_2_overs: self.game.allocation(&Innings::Second).to_string(),
+            team_1_resources: calculation.team_1_resources,
+            team_2_resources: calculation.team_2_resources,
+            formula: calculation.formula.to_string(),
+            par: calculation.par,
+            target: calculation.target,
+            divergence_warning: self
+                .game
+                .divergence_warning(first_innings_total)
+                .map(|warning| warning.to_string()),
+        }
+    }
+
+    fn listing(&self) -> ListedMatch<'_> {
+        ListedMatch {
+            match_id: self.match_id,
+            team_1: &self.team_1,
+            team_2: &self.team_2,
+            creation: self.creation.as_secs(),
+            length: self.game.length().to_string(),
+            g_50: self.game.g_50(),
+            status: self.game.status().to_string(),
+        }
+    }
+
+    /// The first innings total, if its score was recorded or the first innings has finished
+    fn first_innings_total(&self) -> Option<usize> {
+        if let Some(MatchResult::Completed { first, .. }) = &self.result {
+            return Some(first.runs as usize);
+        }
+        if let Some(first) = self
+            .first_innings
+            .as_ref()
+            .filter(|first| self.first_innings_ended(first))
+        {
+            return Some(first.runs as usize);
+        }
+        self.game.events().iter().find_map(|event| match event {
+            MatchEvent::InningsEnd {
+                innings: Innings::First,
+                runs,
+                ..
+            } => Some(*runs as usize),
+            _ => None,
+        })
+    }
+
+    /// Whether the recorded first innings score is the final total: it was recorded as
+    /// complete, all 10 wickets fell, all the overs were faced, or the match has moved on
+    fn first_innings_ended(&self, first: &Score) -> bool {
+        let moved_on = match self.game.status() {
+            MatchStatus::InProgress(innings)
+            | MatchStatus::Stopped(innings)
+            | MatchStatus::Suspended(innings) => innings == Innings::Second,
+            MatchStatus::InningsBreak | MatchStatus::Completed => true,
+            MatchStatus::NotStarted | MatchStatus::Abandoned => false,
+        };
+        moved_on
+            || self.first_innings_complete
+            || first.wickets == 10
+            || first.overs >= self.game.allocation(&Innings::First)
+    }
+
+    fn print_details(&self) {
+        println!(
+            "Match {} between {} and {}",
+            self.match_id, self.team_1, self.team_2
+        );
+        println!("Created {}", format_timestamp(self.creation));
+        println!("Length: {} overs", self.game.length());
+        match &self.grade {
+            Some(grade) => println!(
+                "Grade: {} (G50 {})",
+                grade
+                    .to_possible_value()
+                    .expect("grades are named")
+                    .get_name(),
+                self.game.g_50()
+            ),
+            None => println!("G50: {} (custom)", self.game.g_50()),
+        }
+        println!("Status: {}", self.game.status());
+        for (team, score) in [
+            (&self.team_1, &self.first_innings),
+            (&self.team_2, &self.second_innings),
+        ] {
+            if let Some(score) = score {
+                println!(
+                    "{}: {}/{} ({} overs)",
+                    team, score.runs, score.wickets, score.overs
+                );
+            }
+        }
+
+        let first_innings_total = self.first_innings_total();
+        let calculation = self.game.calculation(first_innings_total.unwrap_or(0));
+        if calculation.interruptions.is_empty() {
+            println!("No interruptions recorded");
+        }
+        for int in &calculation.interruptions {
+            let innings = match int.innings {
+                Innings::First => "First",
+                Innings::Second => "Second",
+            };
+            println!("{} innings: {}", innings, int);
+        }
+        println!(
+            "{} overs available to {} ({:.1}% resources), {} overs available to {} ({:.1}% resources)",
+            self.game.allocation(&Innings::First),
+            self.team_1,
+            calculation.team_1_resources,
+            self.game.allocation(&Innings::Second),
+            self.team_2,
+            calculation.team_2_resources
+        );
+        match first_innings_total {
+            Some(total) => println!(
+                "{} scored {}; target for {} is {} (par {})",
+                self.team_1, total, self.team_2, calculation.target, calculation.par as u32
+            ),
+            None => println!("Target not available until the first innings total is known"),
+        }
+    }
+
+    /// The first innings total given on the command line, falling back to the one recorded
+    fn resolve_first_innings_total(
+        &self,
+        first_innings_total: Option<usize>,
+    ) -> Result<usize, String> {
+        first_innings_total
+            .or_else(|| self.first_innings_total())
+            .ok_or_else(|| UNKNOWN_FIRST_INNINGS_TOTAL.to_string())
+    }
+
+    fn print_comparison(
+        &self,
+        first_innings_total: usize,
+        over_scores: &[u16],
+        curves: &RainRuleCurves,
+    ) {
+        let rules = match self
+            .game
+            .compare_rain_rules(first_innings_total, over_scores, curves)
+        {
+            Ok(rules) => rules,
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        };
+        for rule in rules {
+            match rule.target {
+                Some(target) => println!("{:<35}{}", rule.rule.to_string(), target),
+                None => {
+                    let needs = match rule.rule {
+                        RainRule::Parabola => "needs the parabola table",
+                        RainRule::Vjd => "needs the VJD curves",
+                        _ => "needs over scores",
+                    };
+                    println!("{:<35}{}", rule.rule.to_string(), needs)
+                }
+            }
+        }
+    }
+
+    fn print_what_if(
+        &self,
+        first_innings_total: usize,
+        situation: &Situation,
+        extra_wickets: u16,
+        overs_lost: &[Overs],
+    ) {
+        let grid = self
+            .game
+            .sensitivity(first_innings_total, situation, extra_wickets, overs_lost);
+        print!("{:>8}", "wickets");
+        grid.overs_lost
+            .iter()
+            .for_each(|overs| print!("{:>10}", format!("{} lost", overs)));
+        println!();
+        for (wickets, targets) in grid.wickets.iter().zip(&grid.targets) {
+            print!("{:>8}", wickets);
+            targets.iter().for_each(|target| print!("{:>10}", target));
+            println!();
+        }
+    }
+
+    fn print_projections(&self, situation: &Situation) {
+        for projection in self.game.projections(situation) {
+            let method = match projection.method {
+                ProjectionMethod::G50 => "G50",
+                ProjectionMethod::ResourceRate => "Resource rate",
+                ProjectionMethod::RunRate => "Run rate",
+            };
+            println!("{:<15}{:.0}", method, projection.total);
+        }
     }
 
-    fn calc_target(&self, first_innings_total: usize) -> u32 {
-        self.game.revised_target(first_innings_total)
+    fn print_history(&self, first_innings_total: usize) {
+        let summaries = self.game.interruption_summaries(first_innings_total);
+        let history = self.game.target_history(first_innings_total);
+        if history.is_empty() {
+            println!("No interruptions recorded in the second innings");
+        }
+        for revision in history {
+            println!("{}", summaries[revision.interruption]);
+            println!(
+                "  {} overs available to {}; par {}, target {}",
+                revision.overs, self.team_2, revision.par, revision.target
+            );
+        }
     }
 }
 
@@ -118,52 +894,623 @@ fn main() {
             grade,
             team_1,
             team_2,
+            g_50,
+            divergence_threshold,
+            competition,
         } => {
-            let id = store.keys().max().unwrap_or(&0) + 1;
-            let game = MatchWrapper::new(id, length, grade, team_1, team_2);
-            store.insert(id, game);
+            let id = store.matches.keys().max().unwrap_or(&0) + 1;
+            let mut game = match g_50 {
+                Some(g_50) => CricketMatch::new_with_g_50(length, g_50),
+                None => CricketMatch::new(length, grade.clone()),
+            };
+            let competition_threshold = competition.and_then(|competition| {
+                store
+                    .competitions
+                    .get(&competition)
+                    .unwrap_or_else(|| panic!("competition with id {} not found", competition))
+                    .divergence_threshold
+            });
+            if let Some(threshold) = divergence_threshold.or(competition_threshold) {
+                game = game.with_divergence_threshold(threshold);
+            }
+            let mut game = MatchWrapper::new(id, game, team_1, team_2);
+            game.grade = g_50.is_none().then_some(grade);
+            game.competition = competition;
+            let created = CreatedMatch {
+                match_id: id,
+                team_1: &game.team_1,
+                team_2: &game.team_2,
+            };
+            match args.output {
+                OutputFormat::Text => println!(
+                    "Created match {} between {} and {}",
+                    id, created.team_1, created.team_2
+                ),
+                output => output.write(&created),
+            }
+            store.matches.insert(id, game);
             persist_store(store, args.store_location);
         }
         Commands::Int {
             wickets,
+            runs,
             overs_left,
             overs_lost,
             innings,
+            powerplays,
+            bowling,
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            game.add_int(wickets, runs, overs_left, overs_lost, innings.clone());
+            match args.output {
+                OutputFormat::Text => game.print_revised_limits(&innings, powerplays, bowling),
+                output => output.write(&game.state(&innings, powerplays, bowling)),
+            }
+            persist_store(store, args.store_location);
+        }
+        Commands::Terminate {
+            wickets,
+            runs,
+            overs_bowled,
         } => {
-            retrieve_game(args.id, &mut store).add_int(wickets, overs_left, overs_lost, innings);
+            retrieve_game(args.id, &mut store).terminate(wickets, runs, overs_bowled);
+            persist_store(store, args.store_location);
+        }
+        Commands::Break { overs_lost } => {
+            retrieve_game(args.id, &mut store).add_break(overs_lost);
+            persist_store(store, args.store_location);
+        }
+        Commands::Allocate { overs } => {
+            retrieve_game(args.id, &mut store).allocate(overs);
             persist_store(store, args.store_location);
         }
         Commands::Target {
             first_innings_total,
-        } => println!(
-            "Adjusted target for team 2 is {}",
-            retrieve_game(args.id, &mut store).calc_target(first_innings_total)
-        ),
-        Commands::List => store.values().for_each(|game| {
-            println!(
-                "Match {} between {} and {}",
-                game.match_id, game.team_1, game.team_2
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            let breakdown = game.target_breakdown(or_exit(
+                game.resolve_first_innings_total(first_innings_total),
+            ));
+            match args.output {
+                OutputFormat::Text => {
+                    println!("{}", breakdown);
+                    if let Some(warning) = &breakdown.divergence_warning {
+                        eprintln!("Warning: {}", warning);
+                    }
+                }
+                output => output.write(&breakdown),
+            }
+        }
+        Commands::Explain {
+            first_innings_total,
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            let first_innings_total =
+                or_exit(game.resolve_first_innings_total(first_innings_total));
+            println!("{}", game.game.calculation(first_innings_total))
+        }
+        Commands::History {
+            first_innings_total,
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            game.print_history(or_exit(
+                game.resolve_first_innings_total(first_innings_total),
+            ))
+        }
+        Commands::Compare {
+            first_innings_total,
+            over_scores,
+            parabola,
+            vjd_normal,
+            vjd_target,
+        } => {
+            let curves = RainRuleCurves {
+                parabola: parabola.as_ref().map(read_run_curve),
+                vjd_normal: vjd_normal.as_ref().map(read_run_curve),
+                vjd_target: vjd_target.as_ref().map(read_run_curve),
+            };
+            let game = retrieve_game(args.id, &mut store);
+            game.print_comparison(
+                or_exit(game.resolve_first_innings_total(first_innings_total)),
+                &over_scores,
+                &curves,
             )
-        }),
+        }
+        Commands::Whatif {
+            first_innings_total,
+            wickets,
+            runs,
+            overs_left,
+            innings,
+            extra_wickets,
+            overs_lost,
+        } => {
+            let situation = Situation {
+                innings,
+                wickets,
+                runs,
+                overs_left,
+            };
+            let game = retrieve_game(args.id, &mut store);
+            game.print_what_if(
+                or_exit(game.resolve_first_innings_total(first_innings_total)),
+                &situation,
+                extra_wickets,
+                &overs_lost,
+            )
+        }
+        Commands::Project {
+            wickets,
+            runs,
+            overs_left,
+            innings,
+        } => {
+            let situation = Situation {
+                innings,
+                wickets,
+                runs,
+                overs_left,
+            };
+            retrieve_game(args.id, &mut store).print_projections(&situation)
+        }
+        Commands::Schedule {
+            start,
+            over_rate,
+            interval,
+            extra_time,
+            minimum_overs,
+            delay,
+            innings,
+            wickets,
+            runs,
+            overs_left,
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            let mut schedule = match (game.schedule.take(), start) {
+                (Some(mut schedule), start) => {
+                    schedule.start = start.unwrap_or(schedule.start);
+                    schedule
+                }
+                (None, Some(start)) => Schedule::for_match(start, &game.game),
+                (None, None) => {
+                    eprintln!("No schedule recorded for this match yet; give the start of play");
+                    std::process::exit(1);
+                }
+            };
+            schedule.over_rate = over_rate.unwrap_or(schedule.over_rate);
+            schedule.interval = interval.unwrap_or(schedule.interval);
+            schedule.extra_time = extra_time.unwrap_or(schedule.extra_time);
+            schedule.minimum_overs = minimum_overs.unwrap_or(schedule.minimum_overs);
+            game.print_schedule(&schedule);
+            if let Some(delay) = delay {
+                let situation = Situation {
+                    overs_left: overs_left.unwrap_or_else(|| game.game.allocation(&innings)),
+                    innings,
+                    wickets,
+                    runs,
+                };
+                game.record_delay(&mut schedule, delay, &situation);
+            }
+            game.schedule = Some(schedule);
+            persist_store(store, args.store_location);
+        }
+        Commands::Calibrate { files } => calibrate(&files),
+        Commands::Batch { input, out } => batch(&input, out.as_ref()),
+        Commands::Suspend {
+            wickets,
+            runs,
+            overs_left,
+            innings,
+        } => {
+            retrieve_game(args.id, &mut store).game.suspend(&Situation {
+                innings,
+                wickets,
+                runs,
+                overs_left,
+            });
+            persist_store(store, args.store_location);
+        }
+        Commands::Resume { overs_left } => {
+            let game = retrieve_game(args.id, &mut store);
+            game.game.resume(overs_left);
+            println!(
+                "Play resumes with {} overs available to {} and {} overs available to {}",
+                game.game.allocation(&Innings::First),
+                game.team_1,
+                game.game.allocation(&Innings::Second),
+                game.team_2
+            );
+            persist_store(store, args.store_location);
+        }
+        Commands::Score {
+            innings,
+            runs,
+            wickets,
+            overs,
+            complete,
+        } => {
+            let game = retrieve_game(args.id, &mut store);
+            let score = Score {
+                runs,
+                wickets,
+                overs,
+            };
+            if let Err(err) = game.check_score(&score, &innings) {
+                eprintln!("could not record score: {}", err);
+                std::process::exit(1);
+            }
+            match innings {
+                Innings::First => {
+                    game.first_innings = Some(score);
+                    game.first_innings_complete = complete;
+                }
+                Innings::Second => game.second_innings = Some(score),
+            }
+            match game.first_innings_total() {
+                Some(first_innings_total) => println!(
+                    "Target for {} is {}",
+                    game.team_2,
+                    game.game.calculation(first_innings_total).target
+                ),
+                None => println!("Target not available until the first innings has ended"),
+            }
+            persist_store(store, args.store_location);
+        }
+        Commands::Result {
+            first_runs,
+            first_wickets,
+            first_overs,
+            second_runs,
+            second_wickets,
+            second_overs,
+        } => {
+            let competition_teams = retrieve_game(args.id, &mut store)
+                .competition
+                .and_then(|competition| store.competitions.get(&competition))
+                .map(|competition| competition.teams.clone());
+            let game = retrieve_game(args.id, &mut store);
+            let scores = game
+                .final_score(first_runs, first_wickets, first_overs, &Innings::First)
+                .and_then(|first| {
+                    let second = game.final_score(
+                        second_runs,
+                        second_wickets,
+                        second_overs,
+                        &Innings::Second,
+                    )?;
+                    game.check_result(&first, &second, competition_teams.as_deref())?;
+                    Ok((first, second))
+                });
+            let (first, second) = scores.unwrap_or_else(|err| {
+                eprintln!("could not record result: {}", err);
+                std::process::exit(1)
+            });
+            game.first_innings = Some(first.clone());
+            game.first_innings_complete = true;
+            game.second_innings = Some(second.clone());
+            game.result = Some(MatchResult::Completed { first, second });
+            persist_store(store, args.store_location);
+        }
+        Commands::NoResult => {
+            retrieve_game(args.id, &mut store).result = Some(MatchResult::NoResult);
+            persist_store(store, args.store_location);
+        }
+        Commands::Comp(CompCommands::New {
+            name,
+            teams,
+            win,
+            tie,
+            no_result,
+            loss,
+            bonus,
+            bonus_ratio,
+            divergence_threshold,
+        }) => {
+            let id = store.competitions.keys().max().unwrap_or(&0) + 1;
+            let competition = Competition {
+                competition_id: id,
+                creation: now(),
+                name,
+                teams,
+                rules: PointsRules {
+                    win,
+                    tie,
+                    no_result,
+                    loss,
+                    bonus,
+                    bonus_run_rate_ratio: bonus_ratio,
+                },
+                divergence_threshold,
+            };
+            store.competitions.insert(id, competition);
+            persist_store(store, args.store_location);
+        }
+        Commands::Comp(CompCommands::Table { competition_id }) => {
+            print_standings(&store, competition_id)
+        }
+        Commands::Import(ImportFormat::Cricsheet { file, grade, g_50 }) => {
+            let id = store.matches.keys().max().unwrap_or(&0) + 1;
+            match import_cricsheet(id, &file, grade, g_50) {
+                Ok(game) => {
+                    store.matches.insert(id, game);
+                    persist_store(store, args.store_location);
+                }
+                Err(err) => {
+                    eprintln!("could not import {}: {}", file.display(), err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Show { match_id } => {
+            retrieve_game(match_id.or(args.id), &mut store).print_details()
+        }
+        Commands::List => match args.output {
+            OutputFormat::Text => store.matches.values().for_each(|game| {
+                println!(
+                    "Match {} between {} and {}",
+                    game.match_id, game.team_1, game.team_2
+                )
+            }),
+            output => {
+                let mut matches: Vec<_> =
+                    store.matches.values().map(MatchWrapper::listing).collect();
+                matches.sort_by_key(|game| game.match_id);
+                output.write_all(&matches)
+            }
+        },
         Commands::Delete { match_ids } => {
             let set: HashSet<_> = match_ids.into_iter().collect();
-            store.retain(|k, _| !set.contains(k));
+            let mut deleted: Vec<_> = store
+                .matches
+                .keys()
+                .filter(|id| set.contains(id))
+                .map(|&match_id| DeletedMatch { match_id })
+                .collect();
+            deleted.sort_by_key(|game| game.match_id);
+            store.matches.retain(|k, _| !set.contains(k));
+            match args.output {
+                OutputFormat::Text => deleted
+                    .iter()
+                    .for_each(|game| println!("Deleted match {}", game.match_id)),
+                output => output.write_all(&deleted),
+            }
             persist_store(store, args.store_location);
         }
     }
 }
 
+impl OutputFormat {
+    /// Prints a single result as a JSON object or a CSV row with a header
+    fn write<T: Serialize>(&self, value: &T) {
+        match self {
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string(value).expect("failed to write output")
+            ),
+            _ => self.write_all(std::slice::from_ref(value)),
+        }
+    }
+
+    /// Prints a list of results as a JSON array or CSV rows with a header
+    fn write_all<T: Serialize>(&self, values: &[T]) {
+        match self {
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string(values).expect("failed to write output")
+            ),
+            OutputFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(std::io::stdout());
+                values
+                    .iter()
+                    .for_each(|value| writer.serialize(value).expect("failed to write output"));
+                writer.flush().expect("failed to write output");
+            }
+            OutputFormat::Text => unreachable!("text output is printed by each command"),
+        }
+    }
+}
+
+fn read_run_curve(file: &PathBuf) -> RunCurve {
+    let curve = std::fs::read_to_string(file)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| contents.parse::<RunCurve>().map_err(|err| err.to_string()));
+    match curve {
+        Ok(curve) => curve,
+        Err(err) => {
+            eprintln!("Couldn't read {}: {}", file.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn calibrate(files: &[PathBuf]) {
+    let matches: Vec<_> = files
+        .iter()
+        .filter_map(|file| {
+            let game = File::open(file)
+                .map_err(|err| err.to_string())
+                .and_then(|f| {
+                    CricsheetMatch::from_reader(BufReader::new(f)).map_err(|err| err.to_string())
+                });
+            if let Err(err) = &game {
+                eprintln!("skipping {}: {}", file.display(), err);
+            }
+            game.ok()
+        })
+        .collect();
+
+    match estimate_g_50(&matches) {
+        Some(estimate) => println!(
+            "Suggested G50 is {} (95% confidence interval {:.1} to {:.1}) from {} innings",
+            estimate.suggested(),
+            estimate.lower,
+            estimate.upper,
+            estimate.innings
+        ),
+        None => println!("At least two completed first innings are needed to estimate G50"),
+    }
+}
+
+fn batch(input: &PathBuf, out: Option<&PathBuf>) {
+    let input = BufReader::new(File::open(input).expect("failed to open fixtures"));
+    let summary = match out {
+        Some(out) => recompute_fixtures(
+            input,
+            BufWriter::new(File::create(out).expect("failed to open results for writing")),
+        ),
+        None => recompute_fixtures(input, std::io::stdout().lock()),
+    }
+    .expect("failed to recompute fixtures");
+    eprintln!(
+        "Recomputed {} fixtures, {} could not be calculated",
+        summary.rows, summary.errors
+    );
+}
+
+fn import_cricsheet(
+    id: usize,
+    file: &PathBuf,
+    grade: Grade,
+    g_50: Option<u16>,
+) -> Result<MatchWrapper, String> {
+    let file = File::open(file).map_err(|err| err.to_string())?;
+    let game = CricsheetMatch::from_reader(BufReader::new(file)).map_err(|err| err.to_string())?;
+    let imported = match g_50 {
+        Some(g_50) => game.import_with_g_50(g_50),
+        None => game.import(grade.clone()),
+    }
+    .map_err(|err| err.to_string())?;
+
+    let mut teams = game
+        .regulation_innings()
+        .map(|innings| innings.team.clone());
+    let team_1 = teams.next().unwrap_or_else(|| "Team 1".to_string());
+    let team_2 = teams.next().unwrap_or_else(|| "Team 2".to_string());
+    println!(
+        "Imported as match {}: {} made {}, calculated target for {} is {}",
+        id, team_1, imported.first_innings_total, team_2, imported.computed_target
+    );
+    match (imported.recorded_target, imported.difference()) {
+        (Some(recorded), Some(0)) => println!("Recorded target was also {}", recorded),
+        (Some(recorded), Some(difference)) => println!(
+            "Recorded target was {} ({:+} runs difference)",
+            recorded, difference
+        ),
+        _ => println!("No target was recorded"),
+    }
+
+    let mut game = MatchWrapper::new(id, imported.game, team_1, team_2);
+    game.grade = g_50.is_none().then_some(grade);
+    Ok(game)
+}
+
+/// Prints the error and exits if a command can't go ahead
+fn or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1)
+    })
+}
+
+fn print_standings(store: &Store, competition_id: Option<usize>) {
+    let competition = match competition_id {
+        Some(id) => store
+            .competitions
+            .get(&id)
+            .unwrap_or_else(|| panic!("competition with id {} not found", id)),
+        None => store
+            .competitions
+            .values()
+            .max_by_key(|competition| competition.creation)
+            .expect("no competitions created yet"),
+    };
+
+    let mut standings = Standings::new(competition.rules.clone(), competition.teams.clone());
+    let mut matches: Vec<_> = store
+        .matches
+        .values()
+        .filter(|game| game.competition == Some(competition.competition_id))
+        .collect();
+    matches.sort_by_key(|game| game.match_id);
+    for game in matches {
+        match &game.result {
+            Some(MatchResult::Completed { first, second }) => {
+                standings.add_result(&game.team_1, &game.team_2, &game.game, first, second)
+            }
+            Some(MatchResult::NoResult) => standings.add_no_result(&game.team_1, &game.team_2),
+            None => {}
+        }
+    }
+
+    println!("{}", competition.name);
+    println!(
+        "{:<20}{:>4}{:>4}{:>4}{:>4}{:>4}{:>6}{:>8}",
+        "Team", "P", "W", "L", "T", "NR", "Pts", "NRR"
+    );
+    for row in standings.table() {
+        println!(
+            "{:<20}{:>4}{:>4}{:>4}{:>4}{:>4}{:>6}{:>+8.3}",
+            row.team,
+            row.played,
+            row.won,
+            row.lost,
+            row.tied,
+            row.no_result,
+            row.points,
+            row.net_run_rate.value()
+        );
+    }
+}
+
 fn retrieve_game(id: Option<usize>, store: &mut Store) -> &mut MatchWrapper {
     match id {
         Some(id) => store
+            .matches
             .get_mut(&id)
             .unwrap_or_else(|| panic!("match with id {} not found", id)),
-        None => store.get_mut(&get_latest(store)).unwrap(),
+        None => store.matches.get_mut(&get_latest(store)).unwrap(),
     }
 }
 
+/// Formats a time since the Unix epoch as a UTC date and time, e.g. 2024-06-01 14:30:00 UTC
+fn format_timestamp(time: Duration) -> String {
+    let seconds = time.as_secs();
+    let (days, time_of_day) = ((seconds / 86_400) as i64, seconds % 86_400);
+    // Converts days since the epoch to a civil date using the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+}
+
 fn get_latest(store: &Store) -> usize {
     store
+        .matches
         .values()
         .max_by_key(|value| value.creation)
         .expect("no matches created yet")
@@ -178,8 +1525,153 @@ fn persist_store(store: Store, location: PathBuf) {
 
 fn get_store(location: &PathBuf) -> Store {
     if let Ok(file) = File::open(location) {
-        serde_json::from_reader(BufReader::new(file)).expect("failed to read match details")
+        let store: serde_json::Value =
+            serde_json::from_reader(BufReader::new(file)).expect("failed to read match details");
+        if store.get("matches").is_some() {
+            serde_json::from_value(store).expect("failed to read match details")
+        } else {
+            Store {
+                matches: serde_json::from_value(store).expect("failed to read match details"),
+                competitions: HashMap::new(),
+            }
+        }
     } else {
-        Store::new()
+        Store::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use duckworth_lewis::{CricketMatch, Grade, Innings, MatchOutcome, Overs, Score};
+
+    use crate::{MatchWrapper, UNKNOWN_FIRST_INNINGS_TOTAL};
+
+    fn odi() -> MatchWrapper {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        MatchWrapper::new(1, game, "Team 1".to_string(), "Team 2".to_string())
+    }
+
+    fn score(runs: u16, wickets: u16, overs: &str) -> Score {
+        Score {
+            runs,
+            wickets,
+            overs: Overs::from_str(overs).unwrap(),
+        }
+    }
+
+    /// The text output of the target command shows the same target as its JSON and CSV output
+    fn assert_text_matches_structured_target(game: &MatchWrapper, expected: u32) {
+        let breakdown = game.target_breakdown(250);
+        let json = serde_json::to_value(&breakdown).unwrap();
+
+        assert_eq!(json["target"], expected);
+        assert_eq!(
+            breakdown.to_string(),
+            format!("Adjusted target for team 2 is {}", json["target"])
+        );
+    }
+
+    #[test]
+    fn target_text_matches_structured_output_without_interruptions() {
+        assert_text_matches_structured_target(&odi(), 251);
+    }
+
+    #[test]
+    fn target_text_matches_structured_output_with_interruptions() {
+        let mut game = odi();
+        game.game
+            .interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+
+        assert_text_matches_structured_target(&game, 218);
+    }
+
+    #[test]
+    fn first_innings_in_progress_has_no_total() {
+        let mut game = odi();
+        game.first_innings = Some(score(150, 3, "30"));
+
+        assert!(!game.first_innings_ended(&score(150, 3, "30")));
+        assert_eq!(game.first_innings_total(), None);
+        assert_eq!(
+            game.resolve_first_innings_total(None),
+            Err(UNKNOWN_FIRST_INNINGS_TOTAL.to_string())
+        );
+        assert_eq!(game.resolve_first_innings_total(Some(260)), Ok(260));
+    }
+
+    #[test]
+    fn first_innings_ends_when_complete_bowled_out_out_of_overs_or_moved_on() {
+        let mut game = odi();
+        game.first_innings = Some(score(150, 3, "30"));
+        game.first_innings_complete = true;
+        assert_eq!(game.resolve_first_innings_total(None), Ok(150));
+
+        let game = odi();
+        assert!(game.first_innings_ended(&score(180, 10, "41.2")));
+        assert!(game.first_innings_ended(&score(280, 6, "50")));
+
+        let mut game = odi();
+        game.game
+            .interruption_with_runs(2, 40, Overs::new(40), Overs::new(5), Innings::Second);
+        assert!(game.first_innings_ended(&score(150, 3, "30")));
+    }
+
+    #[test]
+    fn final_score_uses_the_recorded_score_unless_one_is_given() {
+        let mut game = odi();
+        game.second_innings = Some(score(201, 4, "38.1"));
+
+        assert_eq!(
+            game.final_score(None, None, None, &Innings::Second),
+            Ok(score(201, 4, "38.1"))
+        );
+        assert_eq!(
+            game.final_score(Some(199), Some(9), Some(Overs::new(50)), &Innings::Second),
+            Ok(score(199, 9, "50"))
+        );
+        assert!(game.final_score(None, None, None, &Innings::First).is_err());
+        assert!(game
+            .final_score(Some(250), None, None, &Innings::First)
+            .is_err());
+    }
+
+    #[test]
+    fn result_with_more_than_ten_wickets_is_rejected() {
+        let game = odi();
+
+        assert!(game
+            .check_result(&score(250, 11, "50"), &score(200, 10, "45"), None)
+            .is_err());
+        assert!(game
+            .check_result(&score(250, 7, "50"), &score(200, 11, "45"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn result_where_team_2_passes_the_target_is_accepted() {
+        let mut game = odi();
+        game.game
+            .interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        let first = score(250, 7, "50");
+        let second = score(222, 5, "39.3");
+
+        assert!(second.runs as u32 > game.target_breakdown(250).target);
+        assert_eq!(game.check_result(&first, &second, None), Ok(()));
+        assert_eq!(game.game.outcome(&first, &second), MatchOutcome::Team2Won);
+        assert!(game
+            .check_result(&first, &score(222, 5, "40.1"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn result_needs_both_teams_in_the_competition() {
+        let game = odi();
+        let teams = ["Team 1".to_string(), "Team 3".to_string()];
+
+        assert!(game
+            .check_result(&score(250, 7, "50"), &score(200, 10, "45"), Some(&teams))
+            .is_err());
     }
 }
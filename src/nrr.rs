@@ -0,0 +1,171 @@
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Innings};
+use crate::overs::Overs;
+
+/// A team's score at the end of (or during) an innings
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Score {
+    pub runs: u16,
+    pub wickets: u16,
+    pub overs: Overs,
+}
+
+/// The runs and overs each team is credited with for net run rate from a single match, as
+/// returned by `CricketMatch::net_run_rate_credit`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct NetRunRateCredit {
+    pub team_1_runs: u32,
+    pub team_1_overs: Overs,
+    pub team_2_runs: u32,
+    pub team_2_overs: Overs,
+}
+
+/// A team's running net run rate across a number of matches
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct NetRunRate {
+    pub runs_scored: u32,
+    pub balls_faced: u32,
+    pub runs_conceded: u32,
+    pub balls_bowled: u32,
+}
+
+impl NetRunRate {
+    /// Add the credit from a match to the totals of one of the teams that played in it
+    pub fn add(&mut self, credit: &NetRunRateCredit, batted_first: bool) {
+        let (scored, faced, conceded, bowled) = if batted_first {
+            (
+                credit.team_1_runs,
+                &credit.team_1_overs,
+                credit.team_2_runs,
+                &credit.team_2_overs,
+            )
+        } else {
+            (
+                credit.team_2_runs,
+                &credit.team_2_overs,
+                credit.team_1_runs,
+                &credit.team_1_overs,
+            )
+        };
+        self.runs_scored += scored;
+        self.balls_faced += faced.total_balls() as u32;
+        self.runs_conceded += conceded;
+        self.balls_bowled += bowled.total_balls() as u32;
+    }
+
+    /// Runs scored per over less runs conceded per over. Zero until the team has both batted and
+    /// bowled
+    pub fn value(&self) -> f32 {
+        if self.balls_faced == 0 || self.balls_bowled == 0 {
+            return 0.0;
+        }
+        let rate = |runs: u32, balls: u32| runs as f32 * 6.0 / balls as f32;
+        rate(self.runs_scored, self.balls_faced) - rate(self.runs_conceded, self.balls_bowled)
+    }
+}
+
+impl CricketMatch {
+    /// The runs and overs each team is credited with for net run rate, following the ICC's
+    /// playing conditions:
+    /// * a team that is bowled out is credited with the full overs it was allocated, rather than
+    ///   the overs it faced
+    /// * if team 2's target was revised, team 1 is credited with the revised target less one
+    ///   run (i.e. par) off the overs team 2 were allocated, rather than what they actually
+    ///   scored. This includes matches decided at the point of abandonment, where team 2's
+    ///   allocation is the overs they had faced
+    ///
+    /// Matches without a result don't count towards net run rate, so shouldn't be passed here
+    pub fn net_run_rate_credit(&self, first: &Score, second: &Score) -> NetRunRateCredit {
+        let team_2_allocation = self.allocation(&Innings::Second);
+        let overs = |score: &Score, allocation: Overs| match score.wickets {
+            10 => allocation,
+            _ => score.overs.clone(),
+        };
+
+        let (team_1_runs, team_1_overs) = match self.revised_target(first.runs as usize) {
+            0 => (
+                first.runs as u32,
+                overs(first, self.allocation(&Innings::First)),
+            ),
+            target => (target - 1, team_2_allocation.clone()),
+        };
+
+        NetRunRateCredit {
+            team_1_runs,
+            team_1_overs,
+            team_2_runs: second.runs as u32,
+            team_2_overs: overs(second, team_2_allocation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::nrr::{NetRunRate, NetRunRateCredit, Score};
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    fn score(runs: u16, wickets: u16, overs: Overs) -> Score {
+        Score {
+            runs,
+            wickets,
+            overs,
+        }
+    }
+
+    #[test]
+    fn uninterrupted_match_credits_overs_faced() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let credit = game.net_run_rate_credit(
+            &score(250, 10, Overs::new(45)),
+            &score(251, 4, "42.3".parse().unwrap()),
+        );
+
+        assert_eq!(credit.team_1_runs, 250);
+        assert_eq!(credit.team_1_overs, Overs::new(50));
+        assert_eq!(credit.team_2_overs, "42.3".parse().unwrap());
+    }
+
+    /// Based on ICC example three, where team 2's target was revised to 218 off 40 overs
+    #[test]
+    fn revised_target_credits_team_1_with_par() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(10), Innings::Second);
+        let credit = game.net_run_rate_credit(
+            &score(250, 7, Overs::new(50)),
+            &score(180, 10, Overs::new(35)),
+        );
+
+        assert_eq!(
+            credit,
+            NetRunRateCredit {
+                team_1_runs: 217,
+                team_1_overs: Overs::new(40),
+                team_2_runs: 180,
+                team_2_overs: Overs::new(40),
+            }
+        );
+    }
+
+    #[test]
+    fn net_run_rate_combines_matches() {
+        let credit = NetRunRateCredit {
+            team_1_runs: 240,
+            team_1_overs: Overs::new(40),
+            team_2_runs: 200,
+            team_2_overs: Overs::new(40),
+        };
+        let mut team_1 = NetRunRate::default();
+        let mut team_2 = NetRunRate::default();
+        team_1.add(&credit, true);
+        team_2.add(&credit, false);
+
+        assert!((team_1.value() - 1.0).abs() < 1e-4);
+        assert!((team_2.value() + 1.0).abs() < 1e-4);
+        assert_eq!(NetRunRate::default().value(), 0.0);
+    }
+}
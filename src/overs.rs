@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::fmt::{self, Display, Formatter};
 use std::ops::Sub;
 use std::str::FromStr;
 
@@ -27,12 +28,30 @@ impl Overs {
         Overs { overs, balls: 0 }
     }
 
+    /// Constructor for overs from a number of balls, e.g. 45 balls is 7.3 overs
+    pub fn from_balls(balls: u16) -> Overs {
+        Overs {
+            overs: balls / 6,
+            balls: balls % 6,
+        }
+    }
+
     /// The total number of balls that this length of overs contains
     pub fn total_balls(&self) -> u16 {
         self.overs * 6 + self.balls
     }
 }
 
+impl Display for Overs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.balls == 0 {
+            write!(f, "{}", self.overs)
+        } else {
+            write!(f, "{}.{}", self.overs, self.balls)
+        }
+    }
+}
+
 impl From<u16> for Overs {
     fn from(value: u16) -> Self {
         Overs::new(value)
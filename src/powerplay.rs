@@ -0,0 +1,179 @@
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Innings};
+use crate::overs::Overs;
+
+/// The proportion of an innings that makes up the T20I powerplay
+const T20I_POWERPLAY: f32 = 0.3;
+/// The proportion of an innings that makes up each of the first and last ODI powerplays
+const ODI_OUTER_POWERPLAY: f32 = 0.2;
+
+/// The playing conditions used to allocate powerplay overs
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum PowerplayRules {
+    /// Three powerplays: the first and last 20% of the overs (in whole overs, rounded to the
+    /// nearest) with at most 2 and 5 fielders outside the circle, and the rest with at most 4
+    Odi,
+    /// A single powerplay of 30% of the balls (rounded to the nearest ball) with at most 2
+    /// fielders outside the circle
+    T20i,
+}
+
+/// A block of overs with a limit on the fielders allowed outside the circle. Start and end are
+/// the overs completed in the innings when the powerplay starts and ends
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Powerplay {
+    pub start: Overs,
+    pub end: Overs,
+    pub max_fielders_outside_circle: u8,
+}
+
+/// The powerplays for an innings given its current allocation, as returned by
+/// `CricketMatch::powerplays`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct PowerplayAllocation {
+    pub innings: Innings,
+    pub overs: Overs,
+    pub powerplays: Vec<Powerplay>,
+}
+
+impl PowerplayRules {
+    /// The rules usually used for a match of the given length: T20I for matches of 20 overs or
+    /// fewer, ODI otherwise
+    pub fn for_length(length: &Overs) -> PowerplayRules {
+        if length <= &Overs::new(20) {
+            PowerplayRules::T20i
+        } else {
+            PowerplayRules::Odi
+        }
+    }
+}
+
+impl Display for PowerplayAllocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let innings = match self.innings {
+            Innings::First => "First",
+            Innings::Second => "Second",
+        };
+        write!(f, "{} innings powerplays ({} overs):", innings, self.overs)?;
+        for powerplay in &self.powerplays {
+            write!(
+                f,
+                " {} to {} ({} out)",
+                powerplay.start, powerplay.end, powerplay.max_fielders_outside_circle
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl CricketMatch {
+    /// Allocates powerplays for an innings under the given rules, based on the overs currently
+    /// allocated to it after every recorded interruption. This follows the published tables for
+    /// reduced matches, so the allocation can be recalculated after each interruption
+    ///
+    /// Powerplay overs already bowled before an interruption aren't taken into account, so if an
+    /// innings is cut short after its powerplay has finished the officials will need to decide
+    /// how the revised allocation applies
+    pub fn powerplays(&self, innings: &Innings, rules: PowerplayRules) -> PowerplayAllocation {
+        let overs = self.allocation(innings);
+        let balls = overs.total_balls();
+        let powerplays = match rules {
+            PowerplayRules::T20i => {
+                let end = (balls as f32 * T20I_POWERPLAY).round() as u16;
+                vec![Powerplay {
+                    start: Overs::new(0),
+                    end: Overs::from_balls(end),
+                    max_fielders_outside_circle: 2,
+                }]
+            }
+            PowerplayRules::Odi => {
+                let outer = (balls as f32 / 6.0 * ODI_OUTER_POWERPLAY).round() as u16 * 6;
+                let first = Overs::from_balls(outer.min(balls));
+                let last = Overs::from_balls(balls.saturating_sub(outer).max(outer.min(balls)));
+                vec![
+                    Powerplay {
+                        start: Overs::new(0),
+                        end: first.clone(),
+                        max_fielders_outside_circle: 2,
+                    },
+                    Powerplay {
+                        start: first,
+                        end: last.clone(),
+                        max_fielders_outside_circle: 4,
+                    },
+                    Powerplay {
+                        start: last,
+                        end: overs.clone(),
+                        max_fielders_outside_circle: 5,
+                    },
+                ]
+            }
+        };
+
+        PowerplayAllocation {
+            innings: innings.clone(),
+            overs,
+            powerplays,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::powerplay::PowerplayRules;
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    #[test]
+    fn full_odi_has_ten_over_powerplays() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let allocation = game.powerplays(&Innings::First, PowerplayRules::Odi);
+        let ends: Vec<_> = allocation
+            .powerplays
+            .iter()
+            .map(|powerplay| powerplay.end.to_string())
+            .collect();
+
+        assert_eq!(ends, vec!["10", "40", "50"]);
+    }
+
+    #[test]
+    fn reduced_odi_rounds_to_whole_overs() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(1, 55, Overs::new(38), Overs::new(12), Innings::Second);
+        let allocation = game.powerplays(&Innings::Second, PowerplayRules::Odi);
+
+        assert_eq!(allocation.overs, Overs::new(38));
+        assert_eq!(allocation.powerplays[0].end, Overs::new(8));
+        assert_eq!(allocation.powerplays[2].start, Overs::new(30));
+        assert_eq!(
+            allocation.to_string(),
+            "Second innings powerplays (38 overs): 0 to 8 (2 out) 8 to 30 (4 out) 30 to 38 (5 out)"
+        );
+    }
+
+    #[test]
+    fn t20i_powerplay_is_thirty_percent_of_balls() {
+        let mut game = CricketMatch::new(Overs::new(20), Grade::ICCFullMember);
+        assert_eq!(
+            PowerplayRules::for_length(&Overs::new(20)),
+            PowerplayRules::T20i
+        );
+        let full = game.powerplays(&Innings::First, PowerplayRules::T20i);
+        assert_eq!(full.powerplays[0].end, Overs::new(6));
+
+        game.interruption_with_runs(0, 10, Overs::new(18), Overs::new(11), Innings::First);
+        let reduced = game.powerplays(&Innings::First, PowerplayRules::T20i);
+        assert_eq!(reduced.powerplays[0].end, "2.4".parse().unwrap());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::game::{CricketMatch, Innings, Situation};
+
+/// Scales the standard deviation of the runs still to come, which is modelled as this multiple
+/// of the square root of the expected runs. A value of 3 gives a standard deviation of roughly
+/// 47 runs for an average 50 over innings of 245, which is in line with the spread of totals
+/// seen in one day internationals
+const DISPERSION: f32 = 3.0;
+
+impl CricketMatch {
+    /// Estimates the probability that the team batting second reaches their target from the
+    /// given situation. The target is the current `revised_target`, or one more than the first
+    /// innings total if no interruptions have been recorded
+    ///
+    /// Runs still to come are modelled as normally distributed around the expectation implied by
+    /// the resource table: the par score for the whole innings scaled by the proportion of
+    /// team 2's resources that remain. A side that is exactly on par therefore has a little
+    /// under an even chance (they need one more run than par to win). The standard deviation is
+    /// `DISPERSION` multiplied by the square root of the expected runs, so uncertainty shrinks as
+    /// the innings progresses
+    ///
+    /// Ties are counted as not winning. Overs left are limited to the overs currently allocated
+    /// to the second innings
+    ///
+    /// Panics
+    /// The situation must be in the second innings
+    pub fn win_probability(&self, first_innings_total: usize, situation: &Situation) -> f32 {
+        assert_eq!(situation.innings, Innings::Second);
+        let (target, par) = match self.revised_target(first_innings_total) {
+            0 => (first_innings_total as f32 + 1.0, first_innings_total as f32),
+            target => (target as f32, self.revised_par(first_innings_total) as f32),
+        };
+        let needed = target - situation.runs as f32;
+        if needed <= 0.0 {
+            return 1.0;
+        }
+
+        let overs_left = situation
+            .overs_left
+            .clone()
+            .min(self.allocation(&Innings::Second));
+        let remaining = self
+            .table()
+            .resources_remaining(&overs_left, situation.wickets);
+        let total = self.resources(&Innings::Second);
+        if remaining <= 0.0 || total <= 0.0 {
+            return 0.0;
+        }
+
+        let expected = par * remaining / total;
+        let deviation = DISPERSION * expected.sqrt();
+        1.0 - normal_cdf((needed - 0.5 - expected) / deviation)
+    }
+}
+
+/// The cumulative distribution function of the standard normal distribution
+pub(crate) fn normal_cdf(x: f32) -> f32 {
+    0.5 * (1.0 + erf(x / std::f32::consts::SQRT_2))
+}
+
+/// Approximation of the error function from Abramowitz and Stegun (formula 7.1.26), which is
+/// accurate to within 1.5e-7
+fn erf(x: f32) -> f32 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let polynomial = t
+        * (0.254_829_6
+            + t * (-0.284_496_74 + t * (1.421_413_8 + t * (-1.453_152_1 + t * 1.061_405_4))));
+    sign * (1.0 - polynomial * (-x * x).exp())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::probability::normal_cdf;
+    use crate::{CricketMatch, Grade, Innings, Overs, Situation};
+
+    fn situation(wickets: u16, runs: u16, overs_left: u16) -> Situation {
+        Situation {
+            innings: Innings::Second,
+            wickets,
+            runs,
+            overs_left: Overs::new(overs_left),
+        }
+    }
+
+    #[test]
+    fn normal_cdf_is_symmetric() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-3);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-3);
+    }
+
+    #[test]
+    fn chase_at_start_is_close_to_even() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let probability = game.win_probability(250, &situation(0, 0, 50));
+
+        assert!(probability > 0.4 && probability < 0.5);
+    }
+
+    #[test]
+    fn losing_wickets_reduces_probability() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let two_down = game.win_probability(250, &situation(2, 150, 20));
+        let six_down = game.win_probability(250, &situation(6, 150, 20));
+
+        assert!(two_down > six_down);
+    }
+
+    #[test]
+    fn finished_chases_are_certain() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+
+        assert_eq!(game.win_probability(250, &situation(4, 251, 5)), 1.0);
+        assert_eq!(game.win_probability(250, &situation(9, 240, 0)), 0.0);
+    }
+
+    #[test]
+    fn overs_left_are_limited_to_the_allocation() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+
+        assert_eq!(
+            game.win_probability(250, &situation(0, 0, 60)),
+            game.win_probability(250, &situation(0, 0, 50))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Situation};
+
+/// The ways a final innings total can be projected from the current situation
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum ProjectionMethod {
+    /// Runs so far plus the resources remaining scaled by G50 - i.e. assumes the batting side
+    /// will score at an average rate from here
+    G50,
+    /// Runs so far plus the resources remaining scaled by the runs scored per unit of resource
+    /// used so far - i.e. assumes the batting side will keep scoring at the same rate relative
+    /// to the resources they have used
+    ResourceRate,
+    /// Runs so far plus the overs remaining at the current run rate. Takes no account of
+    /// wickets lost, which is why broadcasters tend to prefer the other methods
+    RunRate,
+}
+
+/// A projected total for an innings, as returned by `CricketMatch::projections`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Projection {
+    pub method: ProjectionMethod,
+    pub total: f32,
+}
+
+impl CricketMatch {
+    /// Projects the final total for the innings in the given situation using each of the
+    /// available methods. Resources used so far allow for any interruptions already recorded
+    /// in the innings
+    ///
+    /// If no resources or overs have been used yet the resource rate and run rate projections
+    /// fall back to the G50 projection, as there is no scoring rate to extrapolate from. Overs
+    /// left are limited to the overs currently allocated to the innings
+    pub fn projections(&self, situation: &Situation) -> Vec<Projection> {
+        let runs = situation.runs as f32;
+        let allocation = self.allocation(&situation.innings);
+        let overs_left = situation.overs_left.clone().min(allocation.clone());
+        let remaining = self
+            .table()
+            .resources_remaining(&overs_left, situation.wickets);
+        let used = self.resources(&situation.innings) - remaining;
+        let g_50_total = runs + remaining * self.g_50() / 100.0;
+
+        let resource_rate_total = if used > 0.0 {
+            runs + remaining * runs / used
+        } else {
+            g_50_total
+        };
+
+        let overs_bowled = (allocation - &overs_left).total_balls() as f32 / 6.0;
+        let overs_left = overs_left.total_balls() as f32 / 6.0;
+        let run_rate_total = if overs_bowled > 0.0 {
+            runs + overs_left * runs / overs_bowled
+        } else {
+            g_50_total
+        };
+
+        vec![
+            Projection {
+                method: ProjectionMethod::G50,
+                total: g_50_total,
+            },
+            Projection {
+                method: ProjectionMethod::ResourceRate,
+                total: resource_rate_total,
+            },
+            Projection {
+                method: ProjectionMethod::RunRate,
+                total: run_rate_total,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::ProjectionMethod;
+    use crate::{CricketMatch, Grade, Innings, Overs, Situation};
+
+    fn situation() -> Situation {
+        Situation {
+            innings: Innings::First,
+            wickets: 2,
+            runs: 120,
+            overs_left: Overs::new(30),
+        }
+    }
+
+    #[test]
+    fn projects_with_each_method() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let projections = game.projections(&situation());
+
+        assert_eq!(projections[0].method, ProjectionMethod::G50);
+        assert_eq!(projections[0].total.round(), 285.0);
+        assert_eq!(projections[1].method, ProjectionMethod::ResourceRate);
+        assert_eq!(projections[1].total.round(), 367.0);
+        assert_eq!(projections[2].method, ProjectionMethod::RunRate);
+        assert_eq!(projections[2].total.round(), 300.0);
+    }
+
+    #[test]
+    fn run_rate_falls_back_to_g50_before_first_ball() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = Situation {
+            innings: Innings::First,
+            wickets: 0,
+            runs: 0,
+            overs_left: Overs::new(50),
+        };
+        let projections = game.projections(&situation);
+
+        assert!(projections.iter().all(|p| p.total == 245.0));
+    }
+
+    #[test]
+    fn overs_left_are_limited_to_the_allocation() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = |overs_left| Situation {
+            innings: Innings::First,
+            wickets: 0,
+            runs: 0,
+            overs_left,
+        };
+        let too_many = game.projections(&situation(Overs::new(60)));
+        let full = game.projections(&situation(Overs::new(50)));
+
+        assert!(too_many
+            .iter()
+            .zip(&full)
+            .all(|(too_many, full)| too_many.total == full.total));
+    }
+}
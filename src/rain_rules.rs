@@ -0,0 +1,372 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::event::MatchEvent;
+use crate::game::{CricketMatch, Innings};
+use crate::overs::Overs;
+use crate::DuckworthLewisError;
+
+/// The proportion of the first innings total that Discounted Most Productive Overs takes off for
+/// each over team 2 loses
+const DMPO_DISCOUNT_PER_OVER: f32 = 0.005;
+
+/// Methods that have been used to set targets in shortened matches. Apart from Duckworth Lewis,
+/// these only consider the overs each team had, not when they were lost or how many wickets had
+/// fallen at the time
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum RainRule {
+    /// Duckworth Lewis Standard Edition, as calculated by `CricketMatch::revised_target`
+    DuckworthLewis,
+    /// Team 1's run rate multiplied by the overs team 2 have
+    AverageRunRate,
+    /// The total of team 1's highest scoring overs, taking as many overs as team 2 have (used at
+    /// the 1992 World Cup)
+    MostProductiveOvers,
+    /// Most Productive Overs, less 0.5% of the first innings total for every over team 2 lose
+    DiscountedMostProductiveOvers,
+    /// Team 1's total scaled by the runs the Parabola table expects from the overs team 2 have,
+    /// relative to those it expects from the overs team 1 had
+    Parabola,
+    /// The VJD (Jayadevan) method: team 1's total is scaled up to a full innings using the
+    /// normal curve, and team 2 need the proportion of that given by the target curve for the
+    /// overs they have
+    Vjd,
+}
+
+/// The runs expected from part of an innings, as published for the Parabola and VJD methods.
+/// Entry i is the percentage of a full innings' runs expected once i/n of its overs have been
+/// bowled, where there are n + 1 entries; a table for a 50 over innings therefore has 51
+/// entries, one for each over. Part overs are interpolated between entries
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct RunCurve {
+    percentages: Vec<f32>,
+}
+
+/// The published tables needed by the Parabola and VJD methods. These aren't included with this
+/// crate, so each method gives no target unless its tables are provided
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct RainRuleCurves {
+    /// The Parabola method's table of runs expected from each number of overs
+    pub parabola: Option<RunCurve>,
+    /// The VJD method's normal curve, used when team 1's innings is shortened
+    pub vjd_normal: Option<RunCurve>,
+    /// The VJD method's target curve, used for the overs team 2 have
+    pub vjd_target: Option<RunCurve>,
+}
+
+/// The par score and target a method gives for a match, as returned by
+/// `CricketMatch::compare_rain_rules`. Par is None if the method needs information that wasn't
+/// provided, e.g. the runs team 1 scored in each over
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct RainRuleTarget {
+    pub rule: RainRule,
+    pub par: Option<f32>,
+    pub target: Option<u32>,
+}
+
+impl Display for RainRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RainRule::DuckworthLewis => "Duckworth Lewis",
+            RainRule::AverageRunRate => "Average run rate",
+            RainRule::MostProductiveOvers => "Most productive overs",
+            RainRule::DiscountedMostProductiveOvers => "Discounted most productive overs",
+            RainRule::Parabola => "Parabola",
+            RainRule::Vjd => "VJD",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl RunCurve {
+    /// Returns an error unless there are at least two percentages, starting at 0 and never
+    /// decreasing
+    pub fn new(percentages: Vec<f32>) -> Result<RunCurve, DuckworthLewisError> {
+        let valid = percentages.len() >= 2
+            && percentages[0] == 0.0
+            && percentages.windows(2).all(|pair| pair[0] <= pair[1]);
+        if !valid {
+            return Err(DuckworthLewisError::InvalidRunCurve(format!(
+                "{:?}",
+                percentages
+            )));
+        }
+        Ok(RunCurve { percentages })
+    }
+
+    /// The percentage of a full innings' runs expected once the given proportion of its overs
+    /// have been bowled
+    pub fn at(&self, proportion: f32) -> f32 {
+        let last = self.percentages.len() - 1;
+        let position = proportion.clamp(0.0, 1.0) * last as f32;
+        let below = (position.floor() as usize).min(last);
+        let above = (below + 1).min(last);
+        let fraction = position - below as f32;
+        self.percentages[below] + (self.percentages[above] - self.percentages[below]) * fraction
+    }
+}
+
+impl FromStr for RunCurve {
+    type Err = DuckworthLewisError;
+
+    /// Parses percentages separated by commas or whitespace, e.g. the contents of a file with
+    /// one entry per line
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let percentages = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .parse()
+                    .map_err(|_| DuckworthLewisError::InvalidRunCurve(entry.to_string()))
+            })
+            .collect::<Result<Vec<f32>, _>>()?;
+        RunCurve::new(percentages)
+    }
+}
+
+impl CricketMatch {
+    /// Calculates the target for the team batting second under each of the methods in
+    /// `RainRule`, using the overs each team has been allocated after every recorded
+    /// interruption. Over scores are the runs team 1 scored in each over of their innings, in
+    /// any order, counting a part over as an over; if they aren't known (an empty slice), the
+    /// most productive overs methods give no target. Part overs are ignored when choosing team
+    /// 1's most productive overs
+    ///
+    /// The Parabola and VJD methods use the curves given, looked up at the proportion of the
+    /// match length each team was allocated; either gives no target if its curves are missing.
+    /// VJD's adjustments for wickets lost at an interruption aren't applied
+    ///
+    /// Returns an error if over scores are given but there isn't one for each over team 1 faced
+    /// (their allocation, or the overs bowled if their innings was ended early)
+    pub fn compare_rain_rules(
+        &self,
+        first_innings_total: usize,
+        over_scores: &[u16],
+        curves: &RainRuleCurves,
+    ) -> Result<Vec<RainRuleTarget>, DuckworthLewisError> {
+        let overs_faced = self.first_innings_overs_faced();
+        let expected = overs_faced.total_balls().div_ceil(6) as usize;
+        if !over_scores.is_empty() && over_scores.len() != expected {
+            return Err(DuckworthLewisError::WrongOverScoreCount(
+                expected,
+                over_scores.len(),
+            ));
+        }
+        let total = first_innings_total as f32;
+        let team_1_overs = overs_as_f32(&self.allocation(&Innings::First));
+        let team_2_overs = overs_as_f32(&self.allocation(&Innings::Second));
+        let most_productive = (!over_scores.is_empty())
+            .then(|| most_productive_overs(total, over_scores, team_1_overs, team_2_overs));
+        let length = overs_as_f32(self.length());
+        let (team_1_share, team_2_share) = (team_1_overs / length, team_2_overs / length);
+        let scaled = |team_1: &RunCurve, team_2: &RunCurve| {
+            let team_1 = team_1.at(team_1_share);
+            (team_1 > 0.0).then(|| total * team_2.at(team_2_share) / team_1)
+        };
+
+        let d_l = self.calculation(first_innings_total);
+        let pars = [
+            (
+                RainRule::AverageRunRate,
+                Some(total * team_2_overs / team_1_overs),
+            ),
+            (RainRule::MostProductiveOvers, most_productive),
+            (
+                RainRule::DiscountedMostProductiveOvers,
+                most_productive.map(|par| {
+                    let overs_lost = (team_1_overs - team_2_overs).max(0.0).floor();
+                    par - total * DMPO_DISCOUNT_PER_OVER * overs_lost
+                }),
+            ),
+            (
+                RainRule::Parabola,
+                curves
+                    .parabola
+                    .as_ref()
+                    .and_then(|parabola| scaled(parabola, parabola)),
+            ),
+            (
+                RainRule::Vjd,
+                curves
+                    .vjd_normal
+                    .as_ref()
+                    .zip(curves.vjd_target.as_ref())
+                    .and_then(|(normal, target)| scaled(normal, target)),
+            ),
+        ];
+
+        Ok(std::iter::once(RainRuleTarget {
+            rule: RainRule::DuckworthLewis,
+            par: Some(d_l.par),
+            target: Some(d_l.target),
+        })
+        .chain(pars.into_iter().map(|(rule, par)| RainRuleTarget {
+            rule,
+            par,
+            target: par.map(|par| par.max(0.0).floor() as u32 + 1),
+        }))
+        .collect())
+    }
+
+    /// The overs bowled in the first innings if it has ended, otherwise team 1's allocation
+    fn first_innings_overs_faced(&self) -> Overs {
+        self.events()
+            .iter()
+            .find_map(|event| match event {
+                MatchEvent::InningsEnd {
+                    innings: Innings::First,
+                    overs_bowled,
+                    ..
+                } => Some(overs_bowled.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.allocation(&Innings::First))
+    }
+}
+
+/// The total of team 1's best overs, taking as many as team 2 have whole overs. If team 2 have
+/// at least as many overs as team 1, par is team 1's total
+fn most_productive_overs(
+    total: f32,
+    over_scores: &[u16],
+    team_1_overs: f32,
+    team_2_overs: f32,
+) -> f32 {
+    if team_2_overs >= team_1_overs {
+        return total;
+    }
+    let mut scores = over_scores.to_vec();
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    scores
+        .iter()
+        .take(team_2_overs.floor() as usize)
+        .map(|runs| *runs as f32)
+        .sum()
+}
+
+fn overs_as_f32(overs: &Overs) -> f32 {
+    overs.total_balls() as f32 / 6.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rain_rules::{RainRule, RainRuleCurves, RunCurve};
+    use crate::{CricketMatch, Grade, Innings, Overs};
+    use std::str::FromStr;
+
+    /// Test curves rather than the published tables: 4n - n²/25 percent of the runs from n of 50
+    /// overs, and a straight line
+    fn curves() -> RainRuleCurves {
+        let curved = RunCurve::new(
+            (0..=50)
+                .map(|n| 4.0 * n as f32 - (n * n) as f32 / 25.0)
+                .collect(),
+        )
+        .unwrap();
+        let straight = RunCurve::new((0..=50).map(|n| 2.0 * n as f32).collect()).unwrap();
+        RainRuleCurves {
+            parabola: Some(curved.clone()),
+            vjd_normal: Some(curved),
+            vjd_target: Some(straight),
+        }
+    }
+
+    fn shortened_chase() -> CricketMatch {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        game.interruption_with_runs(0, 0, Overs::new(50), Overs::new(10), Innings::Second);
+        game
+    }
+
+    #[test]
+    fn rules_agree_when_nothing_is_lost() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let targets = game.compare_rain_rules(250, &[5; 50], &curves()).unwrap();
+
+        assert_eq!(targets.len(), 6);
+        assert!(targets.iter().all(|target| target.target == Some(251)));
+    }
+
+    #[test]
+    fn shortened_chase_under_each_rule() {
+        let over_scores: Vec<u16> = (0..50).map(|over| if over < 10 { 1 } else { 6 }).collect();
+        let targets = shortened_chase()
+            .compare_rain_rules(250, &over_scores, &curves())
+            .unwrap();
+        let find = |rule| targets.iter().find(|target| target.rule == rule).unwrap();
+        let target = |rule| find(rule).target;
+
+        assert_eq!(target(RainRule::AverageRunRate), Some(201));
+        assert_eq!(target(RainRule::MostProductiveOvers), Some(241));
+        assert_eq!(target(RainRule::DiscountedMostProductiveOvers), Some(228));
+        assert_eq!(target(RainRule::Parabola), Some(241));
+        assert_eq!(target(RainRule::Vjd), Some(201));
+        assert_eq!(
+            target(RainRule::DuckworthLewis),
+            Some(shortened_chase().revised_target(250))
+        );
+    }
+
+    #[test]
+    fn most_productive_overs_needs_over_scores() {
+        let targets = shortened_chase()
+            .compare_rain_rules(250, &[], &curves())
+            .unwrap();
+
+        assert!(targets
+            .iter()
+            .filter(|target| target.target.is_none())
+            .all(|target| matches!(
+                target.rule,
+                RainRule::MostProductiveOvers | RainRule::DiscountedMostProductiveOvers
+            )));
+        assert_eq!(targets.iter().filter(|t| t.target.is_none()).count(), 2);
+    }
+
+    #[test]
+    fn parabola_and_vjd_need_their_curves() {
+        let vjd_normal_only = RainRuleCurves {
+            vjd_normal: curves().vjd_normal,
+            ..RainRuleCurves::default()
+        };
+        let targets = shortened_chase()
+            .compare_rain_rules(250, &[], &vjd_normal_only)
+            .unwrap();
+        let target = |rule| targets.iter().find(|t| t.rule == rule).unwrap().target;
+
+        assert_eq!(target(RainRule::Parabola), None);
+        assert_eq!(target(RainRule::Vjd), None);
+        assert_eq!(target(RainRule::AverageRunRate), Some(201));
+    }
+
+    #[test]
+    fn run_curves_are_read_and_interpolated() {
+        let curve: RunCurve = "0, 40\n70 100".parse().unwrap();
+
+        assert_eq!(curve.at(0.5), 55.0);
+        assert_eq!(curve.at(1.0), 100.0);
+        assert_eq!(curve.at(2.0), 100.0);
+        assert!("10, 40, 100".parse::<RunCurve>().is_err());
+        assert!("0, 60, 50".parse::<RunCurve>().is_err());
+        assert!("0, sixty".parse::<RunCurve>().is_err());
+    }
+
+    #[test]
+    fn over_scores_must_cover_the_first_innings() {
+        let mut game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+
+        let curves = RainRuleCurves::default();
+
+        assert!(game.compare_rain_rules(250, &[5; 49], &curves).is_err());
+        game.terminate_first_innings(6, 180, Overs::from_str("39.3").unwrap());
+        assert!(game.compare_rain_rules(180, &[5; 50], &curves).is_err());
+        assert!(game.compare_rain_rules(180, &[5; 40], &curves).is_ok());
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Innings, Situation};
+use crate::overs::Overs;
+use crate::DuckworthLewisError;
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A time of day, to the minute. Times after midnight wrap around to the next day
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct ClockTime {
+    minutes: u16,
+}
+
+impl ClockTime {
+    /// Returns an error if the time is too far in the future to count in minutes
+    pub fn new(hours: u16, minutes: u16) -> Result<ClockTime, DuckworthLewisError> {
+        let total = hours
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(minutes))
+            .ok_or_else(|| DuckworthLewisError::InvalidTime(format!("{}:{:02}", hours, minutes)))?;
+        Ok(ClockTime {
+            minutes: total % MINUTES_PER_DAY,
+        })
+    }
+
+    /// The time the given number of minutes later (or earlier, if negative), rounded to the
+    /// nearest minute
+    pub fn add_minutes(&self, minutes: f32) -> ClockTime {
+        let total = self.minutes as i32 + minutes.round() as i32;
+        ClockTime {
+            minutes: total.rem_euclid(MINUTES_PER_DAY as i32) as u16,
+        }
+    }
+}
+
+impl Display for ClockTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.minutes / 60, self.minutes % 60)
+    }
+}
+
+impl FromStr for ClockTime {
+    type Err = DuckworthLewisError;
+
+    /// Parses a 24 hour time in the format HH:MM, e.g. 14:30
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DuckworthLewisError::InvalidTime(s.to_string());
+        let (hours, minutes) = s.trim().split_once(':').ok_or_else(invalid)?;
+        let hours: u16 = hours.parse().map_err(|_| invalid())?;
+        let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+        if hours > 23 || minutes > 59 {
+            return Err(invalid());
+        }
+        ClockTime::new(hours, minutes)
+    }
+}
+
+/// The timings for a day's play. Extra time is the time that can be lost before any overs are
+/// deducted, and minimum overs are the fewest overs each side must face to constitute a match.
+/// Delays recorded with `record_delay` are remembered, so later delays only have the extra time
+/// that is left
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct Schedule {
+    pub start: ClockTime,
+    pub length: Overs,
+    /// Overs expected to be bowled per hour
+    pub over_rate: f32,
+    /// Length of the interval between innings in minutes
+    pub interval: u16,
+    /// Extra time available in minutes
+    pub extra_time: u16,
+    pub minimum_overs: u16,
+    /// Extra time used by the delays recorded so far
+    #[cfg_attr(feature = "ser", serde(default))]
+    pub extra_time_used: u16,
+    /// Minutes the close of play has moved back by the delays recorded so far
+    #[cfg_attr(feature = "ser", serde(default))]
+    pub close_delayed_by: f32,
+}
+
+/// The effect of a delay on the day's play, as returned by `Schedule::revise`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct ScheduleRevision {
+    pub extra_time_used: u16,
+    /// The extra time left for any further delays
+    pub extra_time_remaining: u16,
+    /// Overs lost by each affected innings: both innings for a delay before the second innings,
+    /// otherwise just the second
+    pub overs_lost: Overs,
+    pub close_of_play: ClockTime,
+}
+
+impl Schedule {
+    /// A schedule for the given match, using the ICC's usual timings for its length: for
+    /// matches of 20 overs or fewer, 14.1 overs an hour, a 20 minute interval, 30 minutes' extra
+    /// time and 5 overs a side, otherwise 14.3 overs an hour, a 45 minute interval, 60 minutes'
+    /// extra time and 20 overs a side. The fields can be changed if playing conditions differ
+    pub fn for_match(start: ClockTime, game: &CricketMatch) -> Schedule {
+        let length = game.length().clone();
+        if length <= Overs::new(20) {
+            Schedule {
+                start,
+                length,
+                over_rate: 14.1,
+                interval: 20,
+                extra_time: 30,
+                minimum_overs: 5,
+                extra_time_used: 0,
+                close_delayed_by: 0.0,
+            }
+        } else {
+            Schedule {
+                start,
+                length,
+                over_rate: 14.3,
+                interval: 45,
+                extra_time: 60,
+                minimum_overs: 20,
+                extra_time_used: 0,
+                close_delayed_by: 0.0,
+            }
+        }
+    }
+
+    /// The time it takes to bowl the given overs at the expected over rate, in minutes
+    pub fn minutes_for(&self, overs: &Overs) -> f32 {
+        overs.total_balls() as f32 / 6.0 / self.over_rate * 60.0
+    }
+
+    /// When play is due to finish if there are no delays
+    pub fn scheduled_close(&self) -> ClockTime {
+        self.start
+            .add_minutes(2.0 * self.minutes_for(&self.length) + self.interval as f32)
+    }
+
+    /// The latest time a match can start and still have the minimum overs for each side, using
+    /// all of the extra time
+    pub fn latest_start(&self) -> ClockTime {
+        let minimum = self.minutes_for(&Overs::new(self.minimum_overs));
+        self.scheduled_close()
+            .add_minutes(self.extra_time as f32 - 2.0 * minimum - self.interval as f32)
+    }
+
+    /// The latest time the second innings can start (or resume from the beginning) and still
+    /// have the minimum overs, using all of the extra time
+    pub fn second_innings_cut_off(&self) -> ClockTime {
+        let minimum = self.minutes_for(&Overs::new(self.minimum_overs));
+        self.scheduled_close()
+            .add_minutes(self.extra_time as f32 - minimum)
+    }
+
+    /// Works out how many overs are lost to a delay of the given length, after any delays
+    /// already recorded. Extra time that is left is used first; any further time lost before
+    /// the second innings is shared equally between both innings, and time lost during the
+    /// second innings comes off that innings alone. Overs lost are rounded up to whole overs
+    pub fn revise(&self, minutes_lost: u16, innings: &Innings) -> ScheduleRevision {
+        let extra_time_left = self.extra_time.saturating_sub(self.extra_time_used);
+        let extra_time_used = minutes_lost.min(extra_time_left);
+        let unrecovered = (minutes_lost - extra_time_used) as f32;
+        let overs = unrecovered * self.over_rate / 60.0;
+        let overs_lost = Overs::new((overs / innings_affected(innings)).ceil() as u16);
+        let time_saved = innings_affected(innings) * self.minutes_for(&overs_lost);
+
+        ScheduleRevision {
+            extra_time_used,
+            extra_time_remaining: extra_time_left - extra_time_used,
+            close_of_play: self
+                .scheduled_close()
+                .add_minutes(self.close_delayed_by + minutes_lost as f32 - time_saved),
+            overs_lost,
+        }
+    }
+
+    /// Records a delay against the match as an interruption from the given situation, with the
+    /// overs lost worked out by `revise`, and remembers the extra time used and the new close of
+    /// play. The overs lost are limited to the overs left in the interrupted innings, and the
+    /// revision returned and the new close of play use the overs actually lost
+    pub fn record_delay(
+        &mut self,
+        game: &mut CricketMatch,
+        minutes_lost: u16,
+        situation: &Situation,
+    ) -> ScheduleRevision {
+        let revision = self.revise(minutes_lost, &situation.innings);
+        let overs_lost = revision.overs_lost.min(situation.overs_left.clone());
+        game.interruption_with_runs(
+            situation.wickets,
+            situation.runs,
+            situation.overs_left.clone(),
+            overs_lost.clone(),
+            situation.innings.clone(),
+        );
+        self.extra_time_used += revision.extra_time_used;
+        self.close_delayed_by += minutes_lost as f32
+            - self.minutes_for(&overs_lost) * innings_affected(&situation.innings);
+        ScheduleRevision {
+            overs_lost,
+            close_of_play: self.scheduled_close().add_minutes(self.close_delayed_by),
+            ..revision
+        }
+    }
+}
+
+/// How many innings share the overs lost to a delay during the given innings
+fn innings_affected(innings: &Innings) -> f32 {
+    match innings {
+        Innings::First => 2.0,
+        Innings::Second => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::schedule::{ClockTime, Schedule};
+    use crate::{CricketMatch, Grade, Innings, Overs, Situation};
+
+    fn odi() -> (CricketMatch, Schedule) {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let mut schedule = Schedule::for_match("10:00".parse().unwrap(), &game);
+        schedule.over_rate = 15.0;
+        (game, schedule)
+    }
+
+    #[test]
+    fn parses_and_displays_times() {
+        assert_eq!("09:05".parse::<ClockTime>().unwrap().to_string(), "09:05");
+        assert_eq!(
+            ClockTime::new(23, 30)
+                .unwrap()
+                .add_minutes(45.0)
+                .to_string(),
+            "00:15"
+        );
+        assert!("25:00".parse::<ClockTime>().is_err());
+        assert!("noon".parse::<ClockTime>().is_err());
+        assert!(ClockTime::new(1200, 0).is_err());
+    }
+
+    #[test]
+    fn cut_off_times() {
+        let (_, schedule) = odi();
+
+        assert_eq!(schedule.scheduled_close().to_string(), "17:25");
+        assert_eq!(schedule.latest_start().to_string(), "15:00");
+        assert_eq!(schedule.second_innings_cut_off().to_string(), "17:05");
+    }
+
+    #[test]
+    fn delay_within_extra_time_loses_no_overs() {
+        let (_, schedule) = odi();
+        let revision = schedule.revise(40, &Innings::First);
+
+        assert_eq!(revision.overs_lost, Overs::new(0));
+        assert_eq!(revision.extra_time_remaining, 20);
+        assert_eq!(revision.close_of_play.to_string(), "18:05");
+    }
+
+    #[test]
+    fn longer_delay_is_recorded_against_the_match() {
+        let (mut game, mut schedule) = odi();
+        let situation = Situation {
+            innings: Innings::First,
+            wickets: 1,
+            runs: 48,
+            overs_left: Overs::new(38),
+        };
+        let revision = schedule.record_delay(&mut game, 120, &situation);
+
+        assert_eq!(revision.overs_lost, Overs::new(8));
+        assert_eq!(game.allocation(&Innings::First), Overs::new(42));
+        assert_eq!(game.allocation(&Innings::Second), Overs::new(42));
+        assert_eq!(revision.close_of_play.to_string(), "18:21");
+    }
+
+    #[test]
+    fn extra_time_is_only_used_once() {
+        let (mut game, mut schedule) = odi();
+        let situation = Situation {
+            innings: Innings::First,
+            wickets: 0,
+            runs: 0,
+            overs_left: Overs::new(50),
+        };
+        let first = schedule.record_delay(&mut game, 40, &situation);
+        let second = schedule.record_delay(&mut game, 40, &situation);
+
+        assert_eq!(first.overs_lost, Overs::new(0));
+        assert_eq!(second.extra_time_used, 20);
+        assert_eq!(second.extra_time_remaining, 0);
+        assert_eq!(second.overs_lost, Overs::new(3));
+        assert_eq!(second.close_of_play.to_string(), "18:21");
+    }
+
+    #[test]
+    fn delay_longer_than_the_overs_left_only_loses_those_overs() {
+        let (mut game, mut schedule) = odi();
+        let situation = Situation {
+            innings: Innings::Second,
+            wickets: 4,
+            runs: 150,
+            overs_left: Overs::new(10),
+        };
+        let revision = schedule.record_delay(&mut game, 180, &situation);
+
+        assert_eq!(revision.overs_lost, Overs::new(10));
+        assert_eq!(game.allocation(&Innings::Second), Overs::new(40));
+        assert_eq!(revision.close_of_play.to_string(), "19:45");
+        assert_eq!(
+            schedule
+                .scheduled_close()
+                .add_minutes(schedule.close_delayed_by),
+            revision.close_of_play
+        );
+    }
+}
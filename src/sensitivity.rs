@@ -0,0 +1,147 @@
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::CricketMatch;
+pub use crate::game::Situation;
+use crate::overs::Overs;
+
+/// The targets that would result from a range of hypothetical interruptions, as returned by
+/// `CricketMatch::sensitivity`. Each row of targets corresponds to an entry in wickets, and each
+/// column to an entry in overs lost
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct SensitivityGrid {
+    pub wickets: Vec<u16>,
+    pub overs_lost: Vec<Overs>,
+    pub targets: Vec<Vec<u32>>,
+}
+
+impl SensitivityGrid {
+    /// The target that would result if play was interrupted with the given number of wickets
+    /// down and the given number of overs lost, if that combination was part of the grid
+    pub fn target(&self, wickets: u16, overs_lost: &Overs) -> Option<u32> {
+        let row = self.wickets.iter().position(|w| *w == wickets)?;
+        let column = self.overs_lost.iter().position(|o| o == overs_lost)?;
+        Some(self.targets[row][column])
+    }
+}
+
+impl CricketMatch {
+    /// Evaluates how the target for the team batting second would change if play was interrupted
+    /// from the given situation. Each combination of further wickets (from none up to
+    /// `extra_wickets`, stopping at 9 wickets down) and overs lost is recorded as a hypothetical
+    /// interruption on top of those already entered
+    ///
+    /// The match itself is not altered, so this can be called as often as needed as the
+    /// situation changes. Overs left are limited to the overs currently allocated to the innings,
+    /// and overs lost to the overs left
+    pub fn sensitivity(
+        &self,
+        first_innings_total: usize,
+        situation: &Situation,
+        extra_wickets: u16,
+        overs_lost: &[Overs],
+    ) -> SensitivityGrid {
+        let overs_left = situation
+            .overs_left
+            .clone()
+            .min(self.allocation(&situation.innings));
+        let last_wickets = situation.wickets.saturating_add(extra_wickets).min(9);
+        let wickets: Vec<u16> = (situation.wickets..=last_wickets).collect();
+        let targets = wickets
+            .iter()
+            .map(|wickets| {
+                overs_lost
+                    .iter()
+                    .map(|lost| {
+                        let mut game = self.clone();
+                        game.interruption_with_runs(
+                            *wickets,
+                            situation.runs,
+                            overs_left.clone(),
+                            lost.clone().min(overs_left.clone()),
+                            situation.innings.clone(),
+                        );
+                        game.revised_target(first_innings_total)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        SensitivityGrid {
+            wickets,
+            overs_lost: overs_lost.to_vec(),
+            targets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sensitivity::Situation;
+    use crate::{CricketMatch, Grade, Innings, Overs};
+
+    #[test]
+    fn grid_matches_individual_interruptions() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = Situation {
+            innings: Innings::Second,
+            wickets: 1,
+            runs: 55,
+            overs_left: Overs::new(38),
+        };
+        let grid = game.sensitivity(250, &situation, 2, &[Overs::new(5), Overs::new(10)]);
+
+        assert_eq!(grid.wickets, vec![1, 2, 3]);
+        assert_eq!(grid.target(1, &Overs::new(10)), Some(218));
+        assert!(grid.target(3, &Overs::new(10)).unwrap() > 218);
+        assert!(grid.target(1, &Overs::new(5)).unwrap() > 218);
+        assert_eq!(grid.target(4, &Overs::new(10)), None);
+        assert!(game.events().is_empty());
+    }
+
+    #[test]
+    fn grid_stops_at_nine_wickets() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = Situation {
+            innings: Innings::Second,
+            wickets: 8,
+            runs: 180,
+            overs_left: Overs::new(10),
+        };
+        let grid = game.sensitivity(250, &situation, 3, &[Overs::new(5)]);
+
+        assert_eq!(grid.wickets, vec![8, 9]);
+    }
+
+    #[test]
+    fn large_extra_wickets_do_not_overflow() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = Situation {
+            innings: Innings::Second,
+            wickets: 7,
+            runs: 150,
+            overs_left: Overs::new(10),
+        };
+        let grid = game.sensitivity(250, &situation, u16::MAX, &[Overs::new(5)]);
+
+        assert_eq!(grid.wickets, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn overs_left_are_limited_to_the_allocation() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let situation = |overs_left| Situation {
+            innings: Innings::Second,
+            wickets: 0,
+            runs: 0,
+            overs_left,
+        };
+        let overs_lost = [Overs::new(10), Overs::new(60)];
+        let too_many = game.sensitivity(250, &situation(Overs::new(60)), 0, &overs_lost);
+        let full = game.sensitivity(250, &situation(Overs::new(50)), 0, &overs_lost);
+
+        assert_eq!(too_many.targets, full.targets);
+        assert_eq!(full.target(0, &Overs::new(60)), Some(1));
+    }
+}
@@ -0,0 +1,303 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Serialize};
+
+use crate::game::{CricketMatch, Innings};
+use crate::overs::Overs;
+use crate::table::DuckworthLewisTable;
+
+/// The chance of a wicket falling on any given ball. 0.025 gives an average of seven or eight
+/// wickets in a 50 over innings. The runs expected from each ball are then chosen so that the
+/// runs still to come from any position match the resource table scaled by G50
+const WICKET_PROBABILITY: f32 = 0.025;
+
+/// The runs scored from a ball that isn't a dot ball, with the likelihood of each
+const SCORING_SHOTS: [(u16, f32); 5] = [(1, 0.6), (2, 0.15), (3, 0.02), (4, 0.18), (6, 0.05)];
+
+/// Who won a simulated match
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub enum SimulatedResult {
+    Team1,
+    Team2,
+    Tie,
+}
+
+/// The outcome of a single simulated match
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct SimulatedMatch {
+    pub first_innings_runs: u16,
+    pub first_innings_wickets: u16,
+    pub second_innings_runs: u16,
+    pub second_innings_wickets: u16,
+    /// The target team 2 had when their innings finished
+    pub target: u32,
+    pub interruptions: usize,
+    pub result: SimulatedResult,
+}
+
+/// The outcomes of a batch of simulated matches, as returned by `Simulator::run`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(Serialize, Deserialize))]
+pub struct SimulationReport {
+    pub matches: Vec<SimulatedMatch>,
+}
+
+impl SimulationReport {
+    /// The mean first innings total across all simulated matches, or None if there weren't any
+    pub fn mean_first_innings(&self) -> Option<f32> {
+        self.mean(|game| game.first_innings_runs as f32)
+    }
+
+    /// The mean target set for team 2 across all simulated matches, or None if there weren't any
+    pub fn mean_target(&self) -> Option<f32> {
+        self.mean(|game| game.target as f32)
+    }
+
+    /// The proportion of simulated matches that ended with the given result, or None if there
+    /// weren't any
+    pub fn proportion(&self, result: &SimulatedResult) -> Option<f32> {
+        self.mean(|game| if &game.result == result { 1.0 } else { 0.0 })
+    }
+
+    /// The first innings total that the given proportion of simulated matches fell at or below,
+    /// e.g. 0.5 for the median. None if there weren't any simulated matches
+    pub fn first_innings_percentile(&self, percentile: f32) -> Option<u16> {
+        let mut totals: Vec<_> = self
+            .matches
+            .iter()
+            .map(|game| game.first_innings_runs)
+            .collect();
+        totals.sort_unstable();
+        let index = ((totals.len() as f32 * percentile) as usize).min(totals.len().checked_sub(1)?);
+        Some(totals[index])
+    }
+
+    fn mean(&self, value: impl Fn(&SimulatedMatch) -> f32) -> Option<f32> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        Some(self.matches.iter().map(value).sum::<f32>() / self.matches.len() as f32)
+    }
+}
+
+/// Simulates matches ball by ball, with outcomes drawn so that they are consistent with the
+/// resource table and the G50 of the match being simulated. Randomness is seeded
+/// so a simulation can be repeated exactly
+///
+/// Rain can optionally be added, in which case there is a fixed chance of an interruption at the
+/// end of each over. Interruptions are recorded against the match in the same way as any other,
+/// and team 2's target is revised accordingly
+pub struct Simulator {
+    game: CricketMatch,
+    rng: StdRng,
+    rain_probability: f32,
+    max_overs_lost: u16,
+}
+
+impl Simulator {
+    /// Create a simulator for matches with the same length, G50 and resource table as the given match. Any
+    /// events already recorded for the match are ignored
+    pub fn new(game: &CricketMatch, seed: u64) -> Simulator {
+        Simulator {
+            game: game.as_at(0),
+            rng: StdRng::seed_from_u64(seed),
+            rain_probability: 0.0,
+            max_overs_lost: 0,
+        }
+    }
+
+    /// Add rain to the simulation. Probability is the chance of an interruption at the end of
+    /// each over, and each interruption costs between 1 and `max_overs_lost` overs
+    pub fn with_rain(mut self, probability: f32, max_overs_lost: u16) -> Simulator {
+        self.rain_probability = probability;
+        self.max_overs_lost = max_overs_lost;
+        self
+    }
+
+    /// Simulate the given number of matches
+    pub fn run(&mut self, matches: usize) -> SimulationReport {
+        SimulationReport {
+            matches: (0..matches).map(|_| self.simulate_match()).collect(),
+        }
+    }
+
+    /// Simulate a single match
+    pub fn simulate_match(&mut self) -> SimulatedMatch {
+        let mut game = self.game.clone();
+        let (first_innings_runs, first_innings_wickets) =
+            self.simulate_innings(&mut game, Innings::First, None);
+        let (second_innings_runs, second_innings_wickets) =
+            self.simulate_innings(&mut game, Innings::Second, Some(first_innings_runs));
+
+        let target = target(&game, first_innings_runs);
+        let result = match (second_innings_runs as u32 + 1).cmp(&target) {
+            std::cmp::Ordering::Less => SimulatedResult::Team1,
+            std::cmp::Ordering::Equal => SimulatedResult::Tie,
+            std::cmp::Ordering::Greater => SimulatedResult::Team2,
+        };
+
+        SimulatedMatch {
+            first_innings_runs,
+            first_innings_wickets,
+            second_innings_runs,
+            second_innings_wickets,
+            target,
+            interruptions: game
+                .interruption_summaries(first_innings_runs as usize)
+                .len(),
+            result,
+        }
+    }
+
+    /// Simulates an innings, returning the runs scored and wickets lost. If a first innings total
+    /// is given, the innings ends as soon as the (revised) target is reached
+    fn simulate_innings(
+        &mut self,
+        game: &mut CricketMatch,
+        innings: Innings,
+        first_innings_total: Option<u16>,
+    ) -> (u16, u16) {
+        let g_50 = game.g_50();
+        let mut balls = game.allocation(&innings).total_balls();
+        let mut wickets = 0;
+        let mut runs = 0;
+
+        while balls > 0 && wickets < 10 {
+            if let Some(total) = first_innings_total {
+                if runs as u32 >= target(game, total) {
+                    break;
+                }
+            }
+
+            let expected = expected_runs(game.table(), g_50, balls, wickets);
+            if self.rng.gen::<f32>() < WICKET_PROBABILITY {
+                wickets += 1;
+            } else {
+                let scoring = expected / (1.0 - WICKET_PROBABILITY) / mean_scoring_shot();
+                if self.rng.gen::<f32>() < scoring {
+                    runs += self.scoring_shot();
+                }
+            }
+            balls -= 1;
+
+            if balls % 6 == 0
+                && balls > 0
+                && wickets < 10
+                && self.max_overs_lost > 0
+                && self.rng.gen::<f32>() < self.rain_probability
+            {
+                let overs_lost = self.rng.gen_range(1..=self.max_overs_lost).min(balls / 6);
+                game.interruption_with_runs(
+                    wickets,
+                    runs,
+                    Overs::from_balls(balls),
+                    Overs::new(overs_lost),
+                    innings.clone(),
+                );
+                balls -= overs_lost * 6;
+            }
+        }
+
+        (runs, wickets)
+    }
+
+    fn scoring_shot(&mut self) -> u16 {
+        let mut roll = self.rng.gen::<f32>();
+        for (runs, probability) in SCORING_SHOTS {
+            if roll < probability {
+                return runs;
+            }
+            roll -= probability;
+        }
+        SCORING_SHOTS[0].0
+    }
+}
+
+/// The runs expected from the next ball. The runs still to come from any position are G50
+/// multiplied by the resources remaining, so the runs expected from a ball are the resources it
+/// uses up, plus the resources that would be lost if a wicket fell
+fn expected_runs(table: &DuckworthLewisTable, g_50: f32, balls: u16, wickets: u16) -> f32 {
+    let runs_to_come = |balls, wickets| {
+        g_50 * table.resources_remaining(&Overs::from_balls(balls), wickets) / 100.0
+    };
+    let ball_cost = runs_to_come(balls, wickets) - runs_to_come(balls - 1, wickets);
+    let wicket_cost = runs_to_come(balls - 1, wickets) - runs_to_come(balls - 1, wickets + 1);
+    (ball_cost + WICKET_PROBABILITY * wicket_cost).max(0.0)
+}
+
+fn mean_scoring_shot() -> f32 {
+    SCORING_SHOTS
+        .iter()
+        .map(|(runs, probability)| *runs as f32 * probability)
+        .sum()
+}
+
+/// The target for team 2, which is one more than the first innings total if there have been
+/// no interruptions
+fn target(game: &CricketMatch, first_innings_total: u16) -> u32 {
+    match game.revised_target(first_innings_total as usize) {
+        0 => first_innings_total as u32 + 1,
+        target => target,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::simulation::{SimulatedResult, Simulator};
+    use crate::{CricketMatch, Grade, Overs};
+
+    #[test]
+    fn average_total_matches_g50() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let report = Simulator::new(&game, 7).run(2000);
+
+        assert!((report.mean_first_innings().unwrap() - 245.0).abs() < 15.0);
+    }
+
+    #[test]
+    fn same_seed_gives_same_matches() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let first = Simulator::new(&game, 42).with_rain(0.05, 5).run(20);
+        let second = Simulator::new(&game, 42).with_rain(0.05, 5).run(20);
+
+        let totals = |report: &crate::simulation::SimulationReport| {
+            report
+                .matches
+                .iter()
+                .map(|game| (game.first_innings_runs, game.target))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(totals(&first), totals(&second));
+    }
+
+    #[test]
+    fn rain_revises_targets() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let report = Simulator::new(&game, 3).with_rain(0.1, 5).run(50);
+
+        assert!(report.matches.iter().any(|game| game.interruptions > 0));
+        assert!(report
+            .matches
+            .iter()
+            .any(|game| game.target != game.first_innings_runs as u32 + 1));
+        let proportion = |result| report.proportion(&result).unwrap();
+        let decided = proportion(SimulatedResult::Team1)
+            + proportion(SimulatedResult::Team2)
+            + proportion(SimulatedResult::Tie);
+        assert!((decided - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn empty_report_has_no_statistics() {
+        let game = CricketMatch::new(Overs::new(50), Grade::ICCFullMember);
+        let report = Simulator::new(&game, 1).run(0);
+
+        assert_eq!(report.mean_first_innings(), None);
+        assert_eq!(report.mean_target(), None);
+        assert_eq!(report.first_innings_percentile(0.5), None);
+    }
+}
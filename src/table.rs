@@ -1,9 +1,22 @@
+#[cfg(feature = "ser")]
+use serde::de::Error;
+#[cfg(feature = "ser")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use crate::Overs;
 
-pub const DUCKWORTH_LEWIS_TABLE: DuckworthLewisTable = DuckworthLewisTable::new();
+/// The number of entries in a table - one for each of the 300 balls in a 50 over innings, for each
+/// of 0 to 9 wickets lost
+const TABLE_SIZE: usize = 3000;
+
+pub static DUCKWORTH_LEWIS_TABLE: DuckworthLewisTable = DuckworthLewisTable::new();
 
+/// The percentage of an innings's resources remaining for each combination of balls left and
+/// wickets lost. The standard edition table is used unless a match is given a custom table with
+/// `CricketMatch::with_table`
+#[derive(Debug, Clone, PartialEq)]
 pub struct DuckworthLewisTable {
-    table: [f32; 3000],
+    table: [f32; TABLE_SIZE],
 }
 
 impl DuckworthLewisTable {
@@ -11,6 +24,19 @@ impl DuckworthLewisTable {
         DuckworthLewisTable { table: table() }
     }
 
+    /// Create a custom table. Resources are given as a percentage of those available at the
+    /// start of a 50 over innings, for each number of balls left from 1 to 300 and each number of
+    /// wickets lost from 0 to 9. Resources with no balls left or 10 wickets lost are always 0
+    pub fn from_fn(resources: impl Fn(u16, u16) -> f32) -> DuckworthLewisTable {
+        let mut table = [0.0; TABLE_SIZE];
+        for (index, entry) in table.iter_mut().enumerate() {
+            let balls_left = 300 - (index / 10) as u16;
+            let wickets_lost = (index % 10) as u16;
+            *entry = resources(balls_left, wickets_lost);
+        }
+        DuckworthLewisTable { table }
+    }
+
     pub fn resources_remaining(&self, overs_left: &Overs, wickets_lost: u16) -> f32 {
         assert!(overs_left.overs <= 50);
         assert!(wickets_lost <= 10);
@@ -23,6 +49,24 @@ impl DuckworthLewisTable {
     }
 }
 
+#[cfg(feature = "ser")]
+impl Serialize for DuckworthLewisTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.table.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "ser")]
+impl<'de> Deserialize<'de> for DuckworthLewisTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = Vec::<f32>::deserialize(deserializer)?;
+        let table = entries.try_into().map_err(|entries: Vec<f32>| {
+            D::Error::invalid_length(entries.len(), &"3000 entries")
+        })?;
+        Ok(DuckworthLewisTable { table })
+    }
+}
+
 const fn table() -> [f32; 3000] {
     let mut dlt = [0.0; 3000];
 
@@ -3046,11 +3090,31 @@ mod tests {
         DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(51), 1);
     }
 
+    #[test]
+    fn custom_tables_are_indexed_by_balls_and_wickets() {
+        let custom = DuckworthLewisTable::from_fn(|balls, wickets| balls as f32 - wickets as f32);
+        assert_eq!(298.0, custom.resources_remaining(&Overs::new(50), 2));
+        assert_eq!(43.0, custom.resources_remaining(&Overs::from_balls(45), 2));
+        assert_eq!(0.0, custom.resources_remaining(&Overs::new(0), 0));
+    }
+
     #[test]
     fn resources_remaining_calculates_correct_amount() {
-        assert_eq!(92.6, DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(49), 1));
-        assert_eq!(32.1, DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(11), 3));
-        assert_eq!(19.0, DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(12), 7));
-        assert_eq!(46.4, DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(35), 5));
+        assert_eq!(
+            92.6,
+            DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(49), 1)
+        );
+        assert_eq!(
+            32.1,
+            DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(11), 3)
+        );
+        assert_eq!(
+            19.0,
+            DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(12), 7)
+        );
+        assert_eq!(
+            46.4,
+            DUCKWORTH_LEWIS_TABLE.resources_remaining(&Overs::new(35), 5)
+        );
     }
 }